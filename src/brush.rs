@@ -0,0 +1,370 @@
+//! Freehand brush strokes built from pointer input.
+//!
+//! A [`BrushStroke`] consumes mouse and pen events and emits round stamps
+//! spaced evenly along the path, sized by pen pressure where available.
+//! Pointer positions can be stabilized first; see [`Smoothing`].
+
+use crate::{
+    color::Color,
+    geometry::Point,
+    image::Image,
+    input::{InputEvent, MouseButton, MouseEvent, PenPhase},
+    layers::BlendMode,
+    shape::Shape,
+    smoothing::{Sample, Smoothing, Stabilizer},
+};
+
+/// The active painting tool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tool {
+    Brush(Brush),
+    /// Removes paint under a round tip of the given diameter.
+    Eraser {
+        size: f32,
+    },
+}
+
+impl Tool {
+    /// The brush settings that implement this tool.
+    pub fn brush(&self) -> Brush {
+        match *self {
+            Tool::Brush(brush) => brush,
+            Tool::Eraser { size } => Brush {
+                size,
+                mode: BrushMode::Erase,
+                min_size: 1.0,
+                ..Brush::default()
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BrushMode {
+    /// Paint the brush color over the canvas.
+    #[default]
+    Paint,
+    /// Clear the canvas under the stamp (destination-out).
+    Erase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Brush {
+    /// Stamp diameter in pixels at full pressure.
+    pub size: f32,
+    pub color: Color,
+    /// Distance between stamps as a fraction of the stamp diameter.
+    pub spacing: f32,
+    /// Diameter at zero pressure, as a fraction of `size`.
+    pub min_size: f32,
+    /// Whether pen pressure also scales the stamp opacity.
+    pub pressure_opacity: bool,
+    pub mode: BrushMode,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self {
+            size: 8.0,
+            color: Color::BLACK,
+            spacing: 0.25,
+            min_size: 0.2,
+            pressure_opacity: false,
+            mode: BrushMode::Paint,
+        }
+    }
+}
+
+impl Brush {
+    pub fn new(size: f32, color: Color) -> Self {
+        Self {
+            size,
+            color,
+            ..Self::default()
+        }
+    }
+
+    fn stamp(&self, position: Point, pressure: f32) -> Stamp {
+        let pressure = pressure.clamp(0.0, 1.0);
+        let scale = self.min_size + (1.0 - self.min_size) * pressure;
+        let color = if self.pressure_opacity {
+            self.color.with_alpha(self.color.a * pressure)
+        } else {
+            self.color
+        };
+        Stamp {
+            position,
+            radius: self.size * scale / 2.0,
+            color,
+            mode: self.mode,
+        }
+    }
+}
+
+/// One round dab of paint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stamp {
+    pub position: Point,
+    pub radius: f32,
+    pub color: Color,
+    pub mode: BrushMode,
+}
+
+impl Stamp {
+    pub fn to_shape(&self) -> Shape {
+        Shape::Circle {
+            center: self.position,
+            radius: self.radius,
+        }
+    }
+
+    /// Rasterizes the stamp into `image` with an anti-aliased edge.
+    pub fn apply(&self, image: &mut Image) {
+        let (cx, cy, r) = (self.position.x, self.position.y, self.radius);
+        let x0 = (cx - r - 1.0).floor().max(0.0) as u32;
+        let y0 = (cy - r - 1.0).floor().max(0.0) as u32;
+        let x1 = ((cx + r + 1.0).ceil().max(0.0) as u32).min(image.width());
+        let y1 = ((cy + r + 1.0).ceil().max(0.0) as u32).min(image.height());
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let d = (x as f32 + 0.5 - cx).hypot(y as f32 + 0.5 - cy);
+                let coverage = (r + 0.5 - d).clamp(0.0, 1.0);
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let dst = image.get(x, y).unwrap_or_default();
+                let out = match self.mode {
+                    BrushMode::Paint => BlendMode::Normal.blend(self.color, dst, coverage),
+                    BrushMode::Erase => dst.with_alpha(dst.a * (1.0 - coverage * self.color.a)),
+                };
+                image.set(x, y, out);
+            }
+        }
+    }
+}
+
+/// Turns a pointer drag into evenly spaced brush stamps.
+#[derive(Debug, Clone)]
+pub struct BrushStroke {
+    pub brush: Brush,
+    stabilizer: Stabilizer,
+    last: Option<(Point, f32)>,
+    /// Distance travelled since the last stamp was placed.
+    carry: f32,
+}
+
+impl BrushStroke {
+    pub fn new(brush: Brush) -> Self {
+        Self {
+            brush,
+            stabilizer: Stabilizer::default(),
+            last: None,
+            carry: 0.0,
+        }
+    }
+
+    /// Switches tools; takes effect from the next stamp.
+    pub fn set_tool(&mut self, tool: Tool) {
+        self.brush = tool.brush();
+    }
+
+    /// Sets how pointer positions are smoothed. Changing it mid-stroke
+    /// restarts smoothing from the next point.
+    pub fn set_smoothing(&mut self, smoothing: Smoothing) {
+        self.stabilizer = Stabilizer::new(smoothing);
+    }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.stabilizer.smoothing()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.last.is_some()
+    }
+
+    /// Starts a stroke, returning the initial stamp.
+    pub fn begin(&mut self, position: impl Into<Point>, pressure: f32) -> Stamp {
+        let position = position.into();
+        self.stabilizer.reset();
+        // The first sample of a stroke always passes through unchanged.
+        self.stabilizer.push(position, pressure);
+        self.last = Some((position, pressure));
+        self.carry = 0.0;
+        self.brush.stamp(position, pressure)
+    }
+
+    /// Continues the stroke to `position`, returning the stamps placed along
+    /// the way. Does nothing unless a stroke is active.
+    pub fn extend(&mut self, position: impl Into<Point>, pressure: f32) -> Vec<Stamp> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        let samples = self.stabilizer.push(position, pressure);
+        self.stamp_along(samples)
+    }
+
+    fn stamp_along(&mut self, samples: Vec<Sample>) -> Vec<Stamp> {
+        let mut stamps = Vec::new();
+        for (position, pressure) in samples {
+            stamps.extend(self.line_to(position, pressure));
+        }
+        stamps
+    }
+
+    fn line_to(&mut self, position: Point, pressure: f32) -> Vec<Stamp> {
+        let Some((from, from_pressure)) = self.last else {
+            return Vec::new();
+        };
+        let length = from.distance(position);
+        if length <= 0.0 {
+            return Vec::new();
+        }
+        let mid_pressure = (from_pressure + pressure) / 2.0;
+        let step =
+            (self.brush.stamp(from, mid_pressure).radius * 2.0 * self.brush.spacing).max(0.5);
+        let mut stamps = Vec::new();
+        let mut t = (step - self.carry).max(0.0);
+        while t <= length {
+            let f = t / length;
+            let at = Point::new(
+                from.x + (position.x - from.x) * f,
+                from.y + (position.y - from.y) * f,
+            );
+            let p = from_pressure + (pressure - from_pressure) * f;
+            stamps.push(self.brush.stamp(at, p));
+            t += step;
+        }
+        self.carry = length - (t - step);
+        self.last = Some((position, pressure));
+        stamps
+    }
+
+    /// Ends the stroke, returning the stamps that catch up with the last
+    /// pointer position when smoothing lags behind it. The final stamp always
+    /// lands on that position, even if it is closer than the spacing.
+    pub fn end(&mut self) -> Vec<Stamp> {
+        let samples = self.stabilizer.finish();
+        let mut stamps = self.stamp_along(samples);
+        let Some((position, pressure)) = self.last.take() else {
+            return stamps;
+        };
+        if self.carry > 0.0 {
+            stamps.push(self.brush.stamp(position, pressure));
+        }
+        stamps
+    }
+
+    /// Drives the stroke from raw input: the left mouse button paints at full
+    /// pressure, pens paint with their reported pressure.
+    pub fn handle_event(&mut self, event: &InputEvent) -> Vec<Stamp> {
+        match *event {
+            InputEvent::Mouse(MouseEvent::Pressed {
+                button: MouseButton::Left,
+                position,
+            }) => vec![self.begin(position, 1.0)],
+            InputEvent::Mouse(MouseEvent::Moved { position }) => self.extend(position, 1.0),
+            InputEvent::Mouse(MouseEvent::Released {
+                button: MouseButton::Left,
+                position,
+            }) => {
+                let mut stamps = self.extend(position, 1.0);
+                stamps.extend(self.end());
+                stamps
+            }
+            InputEvent::Pen(pen) => match pen.phase {
+                PenPhase::Down => vec![self.begin(pen.position, pen.pressure)],
+                PenPhase::Moved => self.extend(pen.position, pen.pressure),
+                PenPhase::Up => {
+                    let mut stamps = self.extend(pen.position, pen.pressure);
+                    stamps.extend(self.end());
+                    stamps
+                }
+            },
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_are_evenly_spaced_across_extends() {
+        // Size 8 at spacing 0.25 places a stamp every 2 pixels.
+        let mut stroke = BrushStroke::new(Brush::default());
+        let mut stamps = vec![stroke.begin((0.0, 0.0), 1.0)];
+        for x in [3.0, 7.0, 7.5, 11.0] {
+            stamps.extend(stroke.extend((x, 0.0), 1.0));
+        }
+        let xs: Vec<f32> = stamps.iter().map(|s| s.position.x).collect();
+        assert_eq!(xs, [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        assert!(stamps.iter().all(|s| s.radius == 4.0));
+    }
+
+    #[test]
+    fn the_last_stamp_lands_on_the_pointer() {
+        let mut stroke = BrushStroke::new(Brush::default());
+        stroke.begin((0.0, 0.0), 1.0);
+        stroke.extend((5.0, 0.0), 1.0);
+        let end = stroke.end();
+        assert_eq!(end.last().map(|s| s.position), Some(Point::new(5.0, 0.0)));
+        assert!(!stroke.is_active());
+
+        // A stroke ending exactly on a stamp does not repeat it.
+        stroke.begin((0.0, 0.0), 1.0);
+        stroke.extend((4.0, 0.0), 1.0);
+        assert!(stroke.end().is_empty());
+        assert!(stroke.end().is_empty());
+    }
+
+    #[test]
+    fn smoothed_strokes_end_on_the_pointer() {
+        let mut stroke = BrushStroke::new(Brush::default());
+        stroke.set_smoothing(Smoothing::MovingAverage(4));
+        stroke.begin((0.0, 0.0), 1.0);
+        for i in 1..=10 {
+            stroke.extend((i as f32 * 3.0, (i % 2) as f32), 1.0);
+        }
+        let last = stroke.end().last().map(|s| s.position);
+        assert_eq!(last, Some(Point::new(30.0, 0.0)));
+    }
+
+    #[test]
+    fn eraser_maps_to_an_erase_brush() {
+        let brush = Tool::Eraser { size: 12.0 }.brush();
+        assert_eq!(brush.mode, BrushMode::Erase);
+        assert_eq!(brush.size, 12.0);
+        // Erasers ignore pressure.
+        assert_eq!(brush.stamp(Point::new(0.0, 0.0), 0.0).radius, 6.0);
+
+        let mut stroke = BrushStroke::new(Brush::default());
+        stroke.set_tool(Tool::Eraser { size: 12.0 });
+        assert_eq!(stroke.begin((0.0, 0.0), 0.3).mode, BrushMode::Erase);
+    }
+
+    #[test]
+    fn erasing_reduces_alpha_and_keeps_color() {
+        let paint = Color::rgba(0.2, 0.4, 0.6, 1.0);
+        let mut image = Image::new(9, 9, paint);
+        let stamp = Brush {
+            color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+            ..Tool::Eraser { size: 4.0 }.brush()
+        }
+        .stamp(Point::new(4.5, 4.5), 1.0);
+        stamp.apply(&mut image);
+
+        // A half-strength eraser halves alpha at the center and leaves
+        // pixels outside the stamp untouched; color is never changed.
+        let rgba = paint.to_rgba8();
+        assert_eq!(image.pixels()[4 * 9 + 4], [rgba[0], rgba[1], rgba[2], 128]);
+        assert_eq!(image.pixels()[0], rgba);
+
+        // A fully opaque eraser clears the pixel.
+        Tool::Eraser { size: 4.0 }
+            .brush()
+            .stamp(Point::new(4.5, 4.5), 1.0)
+            .apply(&mut image);
+        assert_eq!(image.pixels()[4 * 9 + 4][3], 0);
+    }
+}
@@ -0,0 +1,128 @@
+//! A 2D camera mapping canvas coordinates to the visible viewport.
+
+use crate::{
+    geometry::{Point, Rect},
+    input::Gesture,
+};
+
+/// Pan and zoom applied to canvas coordinates before they reach the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera {
+    /// Canvas point shown at the center of the viewport.
+    pub center: Point,
+    /// Screen pixels per canvas pixel.
+    pub zoom: f32,
+    /// Viewport size in screen pixels.
+    pub viewport: (f32, f32),
+}
+
+impl Camera {
+    /// The identity view of a `width` × `height` canvas.
+    pub fn new(width: u32, height: u32) -> Self {
+        let (w, h) = (width as f32, height as f32);
+        Self {
+            center: Point::new(w / 2.0, h / 2.0),
+            zoom: 1.0,
+            viewport: (w, h),
+        }
+    }
+
+    pub fn world_to_screen(&self, p: impl Into<Point>) -> Point {
+        let p = p.into();
+        Point::new(
+            (p.x - self.center.x) * self.zoom + self.viewport.0 / 2.0,
+            (p.y - self.center.y) * self.zoom + self.viewport.1 / 2.0,
+        )
+    }
+
+    pub fn screen_to_world(&self, p: impl Into<Point>) -> Point {
+        let p = p.into();
+        Point::new(
+            (p.x - self.viewport.0 / 2.0) / self.zoom + self.center.x,
+            (p.y - self.viewport.1 / 2.0) / self.zoom + self.center.y,
+        )
+    }
+
+    /// The canvas region currently visible.
+    pub fn visible_rect(&self) -> Rect {
+        let (w, h) = (self.viewport.0 / self.zoom, self.viewport.1 / self.zoom);
+        Rect::new(self.center.x - w / 2.0, self.center.y - h / 2.0, w, h)
+    }
+
+    /// Centers on `bounds` and zooms so it fits with `padding` screen pixels
+    /// to spare on every side.
+    pub fn fit(&mut self, bounds: Rect, padding: f32) {
+        let avail_w = (self.viewport.0 - 2.0 * padding).max(1.0);
+        let avail_h = (self.viewport.1 - 2.0 * padding).max(1.0);
+        let zoom_w = if bounds.width > 0.0 {
+            avail_w / bounds.width
+        } else {
+            f32::INFINITY
+        };
+        let zoom_h = if bounds.height > 0.0 {
+            avail_h / bounds.height
+        } else {
+            f32::INFINITY
+        };
+        let zoom = zoom_w.min(zoom_h);
+        self.center = bounds.center();
+        // A single point (or nothing with extent) keeps the current zoom.
+        if zoom.is_finite() {
+            self.zoom = zoom;
+        }
+    }
+
+    /// Interactive navigation: pinch zooms around its center (in screen
+    /// coordinates) and pan drags the view.
+    pub fn apply_gesture(&mut self, gesture: &Gesture) {
+        match *gesture {
+            Gesture::Pinch { scale, center } if scale > 0.0 && scale.is_finite() => {
+                // Keep the canvas point under the pinch center fixed on screen.
+                let anchor = self.screen_to_world(center);
+                self.zoom *= scale;
+                let moved = self.world_to_screen(anchor);
+                self.center.x += (moved.x - center.x) / self.zoom;
+                self.center.y += (moved.y - center.y) / self.zoom;
+            }
+            Gesture::Pinch { .. } => {}
+            Gesture::Pan { delta } => {
+                self.center.x -= delta.x / self.zoom;
+                self.center.y -= delta.y / self.zoom;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pinch_keeps_its_anchor_fixed_on_screen() {
+        let mut camera = Camera::new(800, 600);
+        camera.center = Point::new(130.0, -40.0);
+        camera.zoom = 1.5;
+        let center = Point::new(620.0, 95.0);
+        let anchor = camera.screen_to_world(center);
+        for scale in [2.0, 0.25, 3.0] {
+            camera.apply_gesture(&Gesture::Pinch { scale, center });
+            assert!(camera.world_to_screen(anchor).distance(center) < 1e-3);
+        }
+        assert!((camera.zoom - 2.25).abs() < 1e-5);
+
+        camera.apply_gesture(&Gesture::Pinch { scale: 0.0, center });
+        assert!((camera.zoom - 2.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn pan_moves_the_canvas_with_the_fingers() {
+        let mut camera = Camera::new(800, 600);
+        camera.zoom = 2.0;
+        let p = camera.world_to_screen((10.0, 10.0));
+        camera.apply_gesture(&Gesture::Pan {
+            delta: Point::new(30.0, -8.0),
+        });
+        let moved = camera.world_to_screen((10.0, 10.0));
+        assert_eq!(moved, Point::new(p.x + 30.0, p.y - 8.0));
+    }
+}
@@ -0,0 +1,39 @@
+/// A non-premultiplied RGBA color with components in `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const TRANSPARENT: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
+    pub const BLACK: Color = Color::rgb(0.0, 0.0, 0.0);
+    pub const WHITE: Color = Color::rgb(1.0, 1.0, 1.0);
+    pub const RED: Color = Color::rgb(1.0, 0.0, 0.0);
+    pub const GREEN: Color = Color::rgb(0.0, 1.0, 0.0);
+    pub const BLUE: Color = Color::rgb(0.0, 0.0, 1.0);
+
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn from_rgba8([r, g, b, a]: [u8; 4]) -> Self {
+        let c = |v: u8| v as f32 / 255.0;
+        Self::rgba(c(r), c(g), c(b), c(a))
+    }
+
+    pub fn to_rgba8(self) -> [u8; 4] {
+        let c = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        [c(self.r), c(self.g), c(self.b), c(self.a)]
+    }
+
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+}
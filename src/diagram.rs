@@ -0,0 +1,354 @@
+//! Delaunay triangulations and Voronoi diagrams of point sets.
+
+use std::collections::BTreeSet;
+
+use crate::{
+    geometry::{Point, Rect},
+    shape::Shape,
+};
+
+/// Delaunay triangulation of a point set, as indices into the input points.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Triangulation {
+    /// Triangles, each with a positive signed area (clockwise as seen on the
+    /// y-down canvas).
+    pub triangles: Vec<[usize; 3]>,
+}
+
+impl Triangulation {
+    /// Triangulates `points` with the Bowyer–Watson algorithm.
+    ///
+    /// Duplicate points are triangulated once; fewer than three distinct,
+    /// non-collinear points yield no triangles.
+    pub fn new(points: &[Point]) -> Self {
+        let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x as f64, p.y as f64]).collect();
+        let mut seen = BTreeSet::new();
+        let unique: Vec<usize> = (0..pts.len())
+            .filter(|&i| seen.insert((pts[i][0].to_bits(), pts[i][1].to_bits())))
+            .collect();
+        if unique.len() < 3 {
+            return Self::default();
+        }
+        let (a, b) = (unique[0], unique[1]);
+        let Some(k) = (2..unique.len()).find(|&k| cross(&pts, a, b, pts[unique[k]]) != 0.0) else {
+            return Self::default();
+        };
+        let first = orient(&pts, [a, b, unique[k]]);
+
+        // Instead of a finite super-triangle, the hull is closed off by ghost
+        // triangles sharing a vertex at infinity. Each ghost stands for the
+        // open half-plane beyond its hull edge, so no hull triangles are lost.
+        let mut triangles = vec![first];
+        for j in 0..3 {
+            triangles.push([first[(j + 1) % 3], first[j], INFINITE]);
+        }
+        for (u, &i) in unique.iter().enumerate() {
+            if u < 2 || u == k {
+                continue;
+            }
+            let p = pts[i];
+            let (bad, good): (Vec<_>, Vec<_>) = triangles
+                .into_iter()
+                .partition(|t| in_circumcircle(&pts, t, p));
+            triangles = good;
+
+            // The boundary of the cavity: edges belonging to exactly one bad triangle.
+            let mut edges: Vec<(usize, usize)> = Vec::new();
+            for t in &bad {
+                for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                    if let Some(k) = edges.iter().position(|&(x, y)| (x, y) == (b, a)) {
+                        edges.swap_remove(k);
+                    } else {
+                        edges.push((a, b));
+                    }
+                }
+            }
+            // Keeping the edge direction keeps every triangle's orientation;
+            // ghosts are rotated so the infinite vertex stays last.
+            triangles.extend(edges.into_iter().map(|(a, b)| match (a, b) {
+                (INFINITE, b) => [b, i, INFINITE],
+                (a, INFINITE) => [i, a, INFINITE],
+                (a, b) => [a, b, i],
+            }));
+        }
+
+        triangles.retain(|t| t[2] != INFINITE);
+        Self { triangles }
+    }
+
+    /// Unique undirected edges, each as `(smaller, larger)` index.
+    pub fn edges(&self) -> Vec<(usize, usize)> {
+        let set: BTreeSet<_> = self
+            .triangles
+            .iter()
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+        set.into_iter().collect()
+    }
+
+    /// The triangulation's edges as line shapes.
+    pub fn edge_shapes(&self, points: &[Point]) -> Vec<Shape> {
+        self.edges()
+            .into_iter()
+            .map(|(a, b)| Shape::Line(points[a], points[b]))
+            .collect()
+    }
+
+    pub fn triangle_shapes(&self, points: &[Point]) -> Vec<Shape> {
+        self.triangles
+            .iter()
+            .map(|t| Shape::Polygon(t.iter().map(|&i| points[i]).collect()))
+            .collect()
+    }
+}
+
+/// Index standing for the vertex at infinity shared by ghost triangles.
+const INFINITE: usize = usize::MAX;
+
+/// Twice the signed area of `(a, b, p)`; positive when `p` lies on the
+/// positive side of `a -> b`.
+fn cross(pts: &[[f64; 2]], a: usize, b: usize, p: [f64; 2]) -> f64 {
+    let (pa, pb) = (pts[a], pts[b]);
+    (pb[0] - pa[0]) * (p[1] - pa[1]) - (pb[1] - pa[1]) * (p[0] - pa[0])
+}
+
+fn orient(pts: &[[f64; 2]], [a, b, c]: [usize; 3]) -> [usize; 3] {
+    if cross(pts, a, b, pts[c]) < 0.0 {
+        [a, c, b]
+    } else {
+        [a, b, c]
+    }
+}
+
+fn in_circumcircle(pts: &[[f64; 2]], t: &[usize; 3], p: [f64; 2]) -> bool {
+    if t[2] == INFINITE {
+        // A ghost's circle is the open half-plane beyond its hull edge plus
+        // the open edge itself.
+        let (a, b) = (pts[t[0]], pts[t[1]]);
+        let side = cross(pts, t[0], t[1], p);
+        let along = (p[0] - a[0]) * (b[0] - a[0]) + (p[1] - a[1]) * (b[1] - a[1]);
+        let length2 = (b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2);
+        return side > 0.0 || side == 0.0 && along > 0.0 && along < length2;
+    }
+    let [a, b, c] = t.map(|i| [pts[i][0] - p[0], pts[i][1] - p[1]]);
+    let det = (a[0] * a[0] + a[1] * a[1]) * (b[0] * c[1] - c[0] * b[1])
+        - (b[0] * b[0] + b[1] * b[1]) * (a[0] * c[1] - c[0] * a[1])
+        + (c[0] * c[0] + c[1] * c[1]) * (a[0] * b[1] - b[0] * a[1]);
+    det > 0.0
+}
+
+/// Voronoi cells of `points`, clipped to `rect`, in the same order as the
+/// input. A cell is empty if its site repeats an earlier one or lies outside
+/// `rect`.
+pub fn voronoi_cells(points: &[Point], rect: Rect) -> Vec<Vec<Point>> {
+    let triangulation = Triangulation::new(points);
+    // Only the first occurrence of a repeated site gets a cell, matching
+    // which copy the triangulation keeps.
+    let mut seen = BTreeSet::new();
+    let first: Vec<bool> = points
+        .iter()
+        .map(|p| seen.insert((p.x.to_bits(), p.y.to_bits())))
+        .collect();
+    let mut neighbors = vec![BTreeSet::new(); points.len()];
+    for (a, b) in triangulation.edges() {
+        neighbors[a].insert(b);
+        neighbors[b].insert(a);
+    }
+    if triangulation.triangles.is_empty() {
+        // Degenerate input (fewer than three points or all collinear):
+        // every other distinct site is a potential neighbor.
+        for (i, set) in neighbors.iter_mut().enumerate() {
+            set.extend((0..points.len()).filter(|&j| first[j] && points[j] != points[i]));
+        }
+    }
+
+    let corners = vec![
+        rect.min(),
+        Point::new(rect.x + rect.width, rect.y),
+        rect.max(),
+        Point::new(rect.x, rect.y + rect.height),
+    ];
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &site)| {
+            if !first[i] || !rect.contains(site) {
+                return Vec::new();
+            }
+            neighbors[i].iter().fold(corners.clone(), |cell, &j| {
+                clip_half_plane(&cell, site, points[j])
+            })
+        })
+        .collect()
+}
+
+/// Voronoi cells as polygon shapes, skipping empty cells.
+pub fn voronoi_shapes(points: &[Point], rect: Rect) -> Vec<Shape> {
+    voronoi_cells(points, rect)
+        .into_iter()
+        .filter(|c| c.len() >= 3)
+        .map(Shape::Polygon)
+        .collect()
+}
+
+/// Keeps the part of `polygon` closer to `site` than to `other`.
+fn clip_half_plane(polygon: &[Point], site: Point, other: Point) -> Vec<Point> {
+    let (nx, ny) = (other.x - site.x, other.y - site.y);
+    let mid = Point::new((site.x + other.x) / 2.0, (site.y + other.y) / 2.0);
+    // Negative (or zero) on the `site` side of the bisector.
+    let side = |p: Point| (p.x - mid.x) * nx + (p.y - mid.y) * ny;
+
+    let mut out = Vec::with_capacity(polygon.len() + 1);
+    for (k, &a) in polygon.iter().enumerate() {
+        let b = polygon[(k + 1) % polygon.len()];
+        let (sa, sb) = (side(a), side(b));
+        if sa <= 0.0 {
+            out.push(a);
+        }
+        if (sa < 0.0 && sb > 0.0) || (sa > 0.0 && sb < 0.0) {
+            let t = sa / (sa - sb);
+            out.push(Point::new(a.x + (b.x - a.x) * t, a.y + (b.y - a.y) * t));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::Rng;
+
+    fn random_points(n: usize, seed: u64) -> Vec<Point> {
+        let mut rng = Rng::new(seed);
+        (0..n)
+            .map(|_| Point::new(rng.range(0.0, 1000.0), rng.range(0.0, 1000.0)))
+            .collect()
+    }
+
+    /// Convex hull by monotone chain, counter-clockwise in y-up terms.
+    fn hull(points: &[Point]) -> Vec<Point> {
+        let mut pts = points.to_vec();
+        pts.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        let cross = |o: Point, a: Point, b: Point| {
+            (a.x - o.x) as f64 * (b.y - o.y) as f64 - (a.y - o.y) as f64 * (b.x - o.x) as f64
+        };
+        let mut lower: Vec<Point> = Vec::new();
+        for &p in &pts {
+            while lower.len() >= 2
+                && cross(lower[lower.len() - 2], lower[lower.len() - 1], p) <= 0.0
+            {
+                lower.pop();
+            }
+            lower.push(p);
+        }
+        let mut upper: Vec<Point> = Vec::new();
+        for &p in pts.iter().rev() {
+            while upper.len() >= 2
+                && cross(upper[upper.len() - 2], upper[upper.len() - 1], p) <= 0.0
+            {
+                upper.pop();
+            }
+            upper.push(p);
+        }
+        lower.pop();
+        upper.pop();
+        lower.extend(upper);
+        lower
+    }
+
+    fn area(polygon: &[Point]) -> f64 {
+        let n = polygon.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                a.x as f64 * b.y as f64 - b.x as f64 * a.y as f64
+            })
+            .sum::<f64>()
+            .abs()
+            / 2.0
+    }
+
+    #[test]
+    fn triangle_count_matches_euler() {
+        for seed in 0..5 {
+            let points = random_points(400, seed);
+            let h = hull(&points).len();
+            let triangulation = Triangulation::new(&points);
+            assert_eq!(triangulation.triangles.len(), 2 * points.len() - 2 - h);
+        }
+    }
+
+    #[test]
+    fn triangles_cover_the_hull() {
+        let points = random_points(300, 7);
+        let triangulation = Triangulation::new(&points);
+        let covered: f64 = triangulation
+            .triangles
+            .iter()
+            .map(|t| area(&t.map(|i| points[i])))
+            .sum();
+        let expected = area(&hull(&points));
+        assert!((covered - expected).abs() < expected * 1e-6);
+    }
+
+    #[test]
+    fn triangles_are_positively_oriented_and_empty() {
+        let points = random_points(100, 3);
+        let pts: Vec<[f64; 2]> = points.iter().map(|p| [p.x as f64, p.y as f64]).collect();
+        let triangulation = Triangulation::new(&points);
+        for t in &triangulation.triangles {
+            assert!(cross(&pts, t[0], t[1], pts[t[2]]) > 0.0);
+            for (i, &p) in pts.iter().enumerate() {
+                if !t.contains(&i) {
+                    assert!(!in_circumcircle(&pts, t, p));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_input_has_no_triangles() {
+        assert!(Triangulation::new(&[]).triangles.is_empty());
+        let line: Vec<Point> = (0..5).map(|i| Point::new(i as f32, i as f32)).collect();
+        assert!(Triangulation::new(&line).triangles.is_empty());
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].map(Point::from);
+        assert_eq!(Triangulation::new(&square).triangles.len(), 2);
+    }
+
+    #[test]
+    fn collinear_hull_points_are_connected() {
+        let mut points: Vec<Point> = (0..5).map(|i| Point::new(i as f32, 0.0)).collect();
+        points.push(Point::new(2.0, 3.0));
+        points.push(Point::new(6.0, 0.0));
+        let triangulation = Triangulation::new(&points);
+        assert_eq!(
+            triangulation.triangles.len(),
+            2 * points.len() - 2 - points.len()
+        );
+    }
+
+    #[test]
+    fn voronoi_cells_partition_the_rect() {
+        let points = random_points(50, 11);
+        let rect = Rect::new(0.0, 0.0, 1000.0, 1000.0);
+        let total: f64 = voronoi_cells(&points, rect).iter().map(|c| area(c)).sum();
+        assert!((total - 1_000_000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn degenerate_duplicates_get_one_cell() {
+        let rect = Rect::new(0.0, 0.0, 6.0, 6.0);
+        let points = [(1.0, 1.0), (5.0, 5.0), (3.0, 3.0), (1.0, 1.0)].map(Point::from);
+        let cells = voronoi_cells(&points, rect);
+        assert!(cells[3].is_empty());
+        assert!(cells[..3].iter().all(|c| c.len() >= 3));
+        let total: f64 = cells.iter().map(|c| area(c)).sum();
+        assert!((total - 36.0).abs() < 1e-3);
+
+        let points = [(1.0, 1.0), (1.0, 1.0)].map(Point::from);
+        let cells = voronoi_cells(&points, rect);
+        assert!((area(&cells[0]) - 36.0).abs() < 1e-3);
+        assert!(cells[1].is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! Select-and-drag interaction for moving shapes with the mouse.
+
+use crate::{
+    geometry::Point,
+    input::{InputEvent, MouseButton, MouseEvent},
+    scene::{Scene, ShapeId},
+};
+
+/// What a drag interaction did in response to an input event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragEvent {
+    /// The shape was picked up.
+    Started { id: ShapeId },
+    /// The shape was moved by `delta`.
+    Moved { id: ShapeId, delta: Point },
+    /// The shape was dropped.
+    Ended { id: ShapeId },
+}
+
+/// Lets the left mouse button pick up the topmost shape under the cursor and
+/// drag it around.
+#[derive(Debug, Clone, Default)]
+pub struct ShapeDrag {
+    /// Hit-test tolerance in pixels for lines and points.
+    pub tolerance: f32,
+    grabbed: Option<(ShapeId, Point)>,
+}
+
+impl ShapeDrag {
+    pub fn new(tolerance: f32) -> Self {
+        Self {
+            tolerance,
+            grabbed: None,
+        }
+    }
+
+    /// The shape being dragged, if any.
+    pub fn grabbed(&self) -> Option<ShapeId> {
+        self.grabbed.map(|(id, _)| id)
+    }
+
+    /// Updates the drag from `event`, moving the grabbed shape in `scene`.
+    /// A drag whose shape was removed from the scene ends silently.
+    pub fn handle_event(&mut self, scene: &mut Scene, event: &InputEvent) -> Option<DragEvent> {
+        let InputEvent::Mouse(mouse) = event else {
+            return None;
+        };
+        match *mouse {
+            MouseEvent::Pressed {
+                button: MouseButton::Left,
+                position,
+            } => {
+                let id = *scene.hit_test(position, self.tolerance).first()?;
+                self.grabbed = Some((id, position));
+                Some(DragEvent::Started { id })
+            }
+            MouseEvent::Moved { position } => {
+                let (id, last) = self.grabbed?;
+                let Some(item) = scene.get_mut(id) else {
+                    self.grabbed = None;
+                    return None;
+                };
+                let delta = Point::new(position.x - last.x, position.y - last.y);
+                item.shape.translate(delta.x, delta.y);
+                self.grabbed = Some((id, position));
+                Some(DragEvent::Moved { id, delta })
+            }
+            MouseEvent::Released {
+                button: MouseButton::Left,
+                ..
+            } => {
+                let (id, _) = self.grabbed.take()?;
+                Some(DragEvent::Ended { id })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color::Color, geometry::Rect, shape::Shape};
+
+    fn mouse(event: MouseEvent) -> InputEvent {
+        InputEvent::Mouse(event)
+    }
+
+    #[test]
+    fn drags_the_topmost_scene_shape() {
+        let mut scene = Scene::new();
+        let below = scene.add_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED);
+        let above = scene.add_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::BLUE);
+        let mut drag = ShapeDrag::new(1.0);
+
+        let pressed = MouseEvent::Pressed {
+            button: MouseButton::Left,
+            position: Point::new(5.0, 5.0),
+        };
+        assert_eq!(
+            drag.handle_event(&mut scene, &mouse(pressed)),
+            Some(DragEvent::Started { id: above })
+        );
+        let moved = MouseEvent::Moved {
+            position: Point::new(8.0, 9.0),
+        };
+        drag.handle_event(&mut scene, &mouse(moved));
+        assert_eq!(
+            scene.get(above).unwrap().shape,
+            Shape::Rect(Rect::new(3.0, 4.0, 10.0, 10.0))
+        );
+        assert_eq!(
+            scene.get(below).unwrap().shape,
+            Shape::Rect(Rect::new(0.0, 0.0, 10.0, 10.0))
+        );
+        let released = MouseEvent::Released {
+            button: MouseButton::Left,
+            position: Point::new(8.0, 9.0),
+        };
+        assert_eq!(
+            drag.handle_event(&mut scene, &mouse(released)),
+            Some(DragEvent::Ended { id: above })
+        );
+        assert_eq!(drag.grabbed(), None);
+    }
+}
@@ -0,0 +1,234 @@
+//! Easing curves mapping linear progress `t` in `0.0..=1.0` to eased progress.
+//!
+//! Formulas follow the common easings.net / CSS definitions. Elastic curves
+//! and some cubic-bezier curves overshoot, so results may leave `0.0..=1.0`.
+
+use std::f32::consts::TAU;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    /// A CSS `cubic-bezier(x1, y1, x2, y2)` timing function. The x
+    /// coordinates are clamped to `0.0..=1.0` so the curve stays a function.
+    CubicBezier {
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+    },
+}
+
+impl Easing {
+    pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> Self {
+        Easing::CubicBezier { x1, y1, x2, y2 }
+    }
+
+    /// Eased progress for `t`, which is clamped to `0.0..=1.0`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => t * (2.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::ElasticIn => 1.0 - elastic_out(1.0 - t),
+            Easing::ElasticOut => elastic_out(t),
+            Easing::ElasticInOut => {
+                if t < 0.5 {
+                    (1.0 - elastic_out(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + elastic_out(2.0 * t - 1.0)) / 2.0
+                }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 {
+                    (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0
+                }
+            }
+            Easing::CubicBezier { x1, y1, x2, y2 } => {
+                let (x1, x2) = (x1.clamp(0.0, 1.0), x2.clamp(0.0, 1.0));
+                let s = solve_bezier_x(x1, x2, t);
+                bezier(y1, y2, s)
+            }
+        }
+    }
+}
+
+fn elastic_out(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t;
+    }
+    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * (TAU / 3.0)).sin() + 1.0
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// One coordinate of a cubic Bézier from (0, 0) to (1, 1) with control
+/// coordinates `c1` and `c2`, at parameter `s`.
+fn bezier(c1: f32, c2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * c1 + 3.0 * r * s * s * c2 + s * s * s
+}
+
+/// The curve parameter whose x coordinate is `x`: a few Newton steps, then
+/// bisection if they did not converge.
+fn solve_bezier_x(x1: f32, x2: f32, x: f32) -> f32 {
+    const EPSILON: f32 = 1e-6;
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier(x1, x2, s) - x;
+        if error.abs() < EPSILON {
+            return s;
+        }
+        let r = 1.0 - s;
+        let slope = 3.0 * r * r * x1 + 6.0 * r * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+        if slope.abs() < EPSILON {
+            break;
+        }
+        s -= error / slope;
+    }
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = bezier(x1, x2, s);
+        if (value - x).abs() < EPSILON {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Easing; 14] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+        Easing::CubicBezier {
+            x1: 0.25,
+            y1: 0.1,
+            x2: 0.25,
+            y2: 1.0,
+        },
+    ];
+
+    #[test]
+    fn endpoints_are_fixed() {
+        for easing in ALL {
+            assert!(easing.apply(0.0).abs() < 1e-5, "{easing:?}");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{easing:?}");
+            assert_eq!(easing.apply(-1.0), easing.apply(0.0), "{easing:?}");
+            assert_eq!(easing.apply(2.0), easing.apply(1.0), "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn in_out_curves_are_symmetric() {
+        for easing in [
+            Easing::QuadInOut,
+            Easing::CubicInOut,
+            Easing::ElasticInOut,
+            Easing::BounceInOut,
+        ] {
+            for i in 0..=10 {
+                let t = i as f32 / 10.0;
+                let mirrored = 1.0 - easing.apply(1.0 - t);
+                assert!(
+                    (easing.apply(t) - mirrored).abs() < 1e-5,
+                    "{easing:?} at {t}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_matches_css_keywords() {
+        // `linear` and the CSS `ease` curve at known points.
+        let linear = Easing::cubic_bezier(0.0, 0.0, 1.0, 1.0);
+        let ease = Easing::cubic_bezier(0.25, 0.1, 0.25, 1.0);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!((linear.apply(t) - t).abs() < 1e-4);
+        }
+        assert!((ease.apply(0.25) - 0.4094).abs() < 1e-3);
+        assert!((ease.apply(0.5) - 0.8024).abs() < 1e-3);
+    }
+
+    #[test]
+    fn cubic_bezier_solves_steep_curves() {
+        // Flat start and end make Newton's method stall; bisection takes over.
+        let steep = Easing::cubic_bezier(1.0, 0.0, 0.0, 1.0);
+        let mut last = 0.0;
+        for i in 0..=100 {
+            let y = steep.apply(i as f32 / 100.0);
+            assert!(y >= last - 1e-5);
+            last = y;
+        }
+        assert!((steep.apply(0.5) - 0.5).abs() < 1e-3);
+    }
+}
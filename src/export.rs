@@ -0,0 +1,218 @@
+//! Framing options shared by every exporter.
+//!
+//! Raster exporters pass their image through [`ExportOptions::apply`]; vector
+//! exporters such as [`svg_document`](crate::trace::svg_document) use
+//! [`ExportOptions::frame`] to pick their view box, so both trim, pad and
+//! scale the same way.
+
+use crate::{color::Color, geometry::Rect, image::Image, layers::BlendMode};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExportOptions {
+    /// Crop to the bounds of the non-transparent content.
+    pub trim_to_content: bool,
+    /// Margin added around the (possibly trimmed) content, in canvas pixels.
+    pub padding: u32,
+    /// Flattens the result onto this color; `None` keeps transparency.
+    pub background: Option<Color>,
+    /// Output pixels per canvas pixel.
+    pub scale: f32,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            trim_to_content: false,
+            padding: 0,
+            background: None,
+            scale: 1.0,
+        }
+    }
+}
+
+impl ExportOptions {
+    /// The canvas region to export, given the full canvas and the content
+    /// bounds (if any content exists).
+    pub fn frame(&self, canvas: Rect, content: Option<Rect>) -> Rect {
+        let base = match (self.trim_to_content, content) {
+            (true, Some(content)) => content,
+            _ => canvas,
+        };
+        let pad = self.padding as f32;
+        Rect::new(
+            base.x - pad,
+            base.y - pad,
+            base.width + 2.0 * pad,
+            base.height + 2.0 * pad,
+        )
+    }
+
+    /// The scale actually applied; non-positive or non-finite values mean 1.
+    pub(crate) fn output_scale(&self) -> f32 {
+        if self.scale > 0.0 && self.scale.is_finite() {
+            self.scale
+        } else {
+            1.0
+        }
+    }
+
+    /// Applies trimming, padding, background and scale to a rendered image.
+    pub fn apply(&self, image: &Image) -> Image {
+        let canvas = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        let frame = self.frame(canvas, opaque_bounds(image));
+
+        let (fw, fh) = (frame.width as u32, frame.height as u32);
+        let mut framed = Image::new(fw, fh, self.background.unwrap_or(Color::TRANSPARENT));
+        for y in 0..fh {
+            for x in 0..fw {
+                let (sx, sy) = (x as i64 + frame.x as i64, y as i64 + frame.y as i64);
+                if sx < 0 || sy < 0 {
+                    continue;
+                }
+                let Some(src) = image.get(sx as u32, sy as u32) else {
+                    continue;
+                };
+                let out = match self.background {
+                    Some(bg) => BlendMode::Normal.blend(src, bg, 1.0),
+                    None => src,
+                };
+                framed.set(x, y, out);
+            }
+        }
+
+        let scale = self.output_scale();
+        if scale == 1.0 {
+            framed
+        } else {
+            let w = (fw as f32 * scale).round().max(1.0) as u32;
+            let h = (fh as f32 * scale).round().max(1.0) as u32;
+            framed.resized(w, h)
+        }
+    }
+}
+
+/// Pixel bounds of everything that is not fully transparent.
+fn opaque_bounds(image: &Image) -> Option<Rect> {
+    let w = image.width() as usize;
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+    for (i, p) in image.pixels().iter().enumerate() {
+        if p[3] > 0 {
+            let (x, y) = (i % w, i / w);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+    }
+    (min_x != usize::MAX).then(|| {
+        Rect::new(
+            min_x as f32,
+            min_y as f32,
+            (max_x - min_x + 1) as f32,
+            (max_y - min_y + 1) as f32,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 10×8 transparent canvas with a red 2×3 block at (4, 2).
+    fn canvas() -> Image {
+        let mut image = Image::new(10, 8, Color::TRANSPARENT);
+        for y in 2..5 {
+            for x in 4..6 {
+                image.set(x, y, Color::RED);
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn default_options_export_the_canvas_unchanged() {
+        let image = canvas();
+        assert_eq!(ExportOptions::default().apply(&image), image);
+    }
+
+    #[test]
+    fn trim_crops_to_the_content() {
+        let options = ExportOptions {
+            trim_to_content: true,
+            ..Default::default()
+        };
+        let out = options.apply(&canvas());
+        assert_eq!((out.width(), out.height()), (2, 3));
+        assert!(out.pixels().iter().all(|p| *p == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn trim_without_content_keeps_the_full_canvas() {
+        let options = ExportOptions {
+            trim_to_content: true,
+            ..Default::default()
+        };
+        let empty = Image::new(10, 8, Color::TRANSPARENT);
+        assert_eq!(options.apply(&empty), empty);
+    }
+
+    #[test]
+    fn padding_adds_a_transparent_margin() {
+        let options = ExportOptions {
+            trim_to_content: true,
+            padding: 1,
+            ..Default::default()
+        };
+        let out = options.apply(&canvas());
+        assert_eq!((out.width(), out.height()), (4, 5));
+        assert_eq!(out.get(0, 0), Some(Color::TRANSPARENT));
+        assert_eq!(out.get(1, 1), Some(Color::RED));
+        assert_eq!(out.get(3, 4), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn padding_past_the_canvas_edge_stays_transparent() {
+        let options = ExportOptions {
+            padding: 2,
+            ..Default::default()
+        };
+        let out = options.apply(&canvas());
+        assert_eq!((out.width(), out.height()), (14, 12));
+        assert_eq!(out.get(6, 4), Some(Color::RED));
+        assert_eq!(out.pixels().iter().filter(|p| p[3] > 0).count(), 6);
+    }
+
+    #[test]
+    fn background_flattens_translucent_pixels() {
+        let mut image = Image::new(2, 1, Color::TRANSPARENT);
+        image.set(1, 0, Color::rgba(0.0, 0.0, 0.0, 0.5));
+        let options = ExportOptions {
+            background: Some(Color::WHITE),
+            ..Default::default()
+        };
+        let out = options.apply(&image);
+        assert_eq!(out.pixels()[0], [255, 255, 255, 255]);
+        // Half-transparent black over white is opaque mid gray.
+        let [r, g, b, a] = out.pixels()[1];
+        assert_eq!(a, 255);
+        assert!(r.abs_diff(128) <= 1 && r == g && g == b);
+    }
+
+    #[test]
+    fn scale_resizes_the_framed_image() {
+        let options = ExportOptions {
+            trim_to_content: true,
+            scale: 2.0,
+            ..Default::default()
+        };
+        let out = options.apply(&canvas());
+        assert_eq!((out.width(), out.height()), (4, 6));
+        assert!(out.pixels().iter().all(|p| *p == [255, 0, 0, 255]));
+
+        let invalid = ExportOptions {
+            scale: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(invalid.apply(&canvas()), canvas());
+    }
+}
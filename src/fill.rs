@@ -0,0 +1,118 @@
+//! Flood fill (paint bucket) on raster images.
+
+use crate::{color::Color, image::Image, mask::Mask};
+
+/// The 4-connected region around `(x, y)` whose colors lie within
+/// `tolerance` of the seed color.
+///
+/// `tolerance` is the largest allowed per-channel difference (RGBA, in
+/// `0.0..=1.0`); `0.0` selects exactly matching pixels only. A seed outside the
+/// image yields an empty mask.
+pub fn flood_region(image: &Image, x: u32, y: u32, tolerance: f32) -> Mask {
+    let (w, h) = (image.width(), image.height());
+    let mut mask = Mask::new(w, h);
+    let Some(seed) = image.get(x, y) else {
+        return mask;
+    };
+    let limit = (tolerance.clamp(0.0, 1.0) * 255.0).round() as i32;
+    let seed = seed.to_rgba8();
+    let pixels = image.pixels();
+    let matches = |x: u32, y: u32| {
+        let p = pixels[y as usize * w as usize + x as usize];
+        (0..4).all(|c| (p[c] as i32 - seed[c] as i32).abs() <= limit)
+    };
+
+    // Scanline fill: grow each seed into a horizontal span, then queue the
+    // rows above and below.
+    let mut stack = vec![(x, y)];
+    while let Some((x, y)) = stack.pop() {
+        if mask.get(x, y) || !matches(x, y) {
+            continue;
+        }
+        let mut left = x;
+        while left > 0 && !mask.get(left - 1, y) && matches(left - 1, y) {
+            left -= 1;
+        }
+        let mut right = x;
+        while right + 1 < w && !mask.get(right + 1, y) && matches(right + 1, y) {
+            right += 1;
+        }
+        for sx in left..=right {
+            mask.set(sx, y, true);
+            if y > 0 {
+                stack.push((sx, y - 1));
+            }
+            if y + 1 < h {
+                stack.push((sx, y + 1));
+            }
+        }
+    }
+    mask
+}
+
+/// Paints `color` over the region [`flood_region`] selects and returns that
+/// region.
+pub fn flood_fill(image: &mut Image, x: u32, y: u32, color: Color, tolerance: f32) -> Mask {
+    let mask = flood_region(image, x, y, tolerance);
+    let rgba = color.to_rgba8();
+    let w = image.width() as usize;
+    let pixels = image.pixels_mut();
+    for (x, y) in mask.iter() {
+        pixels[y as usize * w + x as usize] = rgba;
+    }
+    mask
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A white 10×10 image split by a black vertical wall at x = 4 with a
+    /// one-pixel gap at the bottom when `gap` is set.
+    fn walled(gap: bool) -> Image {
+        let mut image = Image::new(10, 10, Color::WHITE);
+        for y in 0..10 {
+            if !(gap && y == 9) {
+                image.set(4, y, Color::BLACK);
+            }
+        }
+        image
+    }
+
+    #[test]
+    fn stops_at_barriers() {
+        let region = flood_region(&walled(false), 0, 0, 0.0);
+        assert_eq!(region.count(), 40);
+        assert!(region.iter().all(|(x, _)| x < 4));
+    }
+
+    #[test]
+    fn leaks_through_gaps() {
+        let region = flood_region(&walled(true), 0, 0, 0.0);
+        assert_eq!(region.count(), 91);
+        assert!(region.get(9, 0));
+    }
+
+    #[test]
+    fn tolerance_admits_similar_colors() {
+        let mut image = Image::new(4, 1, Color::WHITE);
+        image.set(2, 0, Color::rgb(0.95, 0.95, 0.95));
+        assert_eq!(flood_region(&image, 0, 0, 0.0).count(), 2);
+        assert_eq!(flood_region(&image, 0, 0, 0.1).count(), 4);
+    }
+
+    #[test]
+    fn fill_paints_only_the_region() {
+        let mut image = walled(false);
+        let region = flood_fill(&mut image, 9, 9, Color::RED, 0.0);
+        assert_eq!(region.count(), 50);
+        assert_eq!(image.get(9, 0), Some(Color::RED));
+        assert_eq!(image.get(0, 0), Some(Color::WHITE));
+        assert_eq!(image.get(4, 0), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn seed_outside_the_image_selects_nothing() {
+        assert_eq!(flood_region(&walled(false), 10, 0, 1.0).count(), 0);
+    }
+}
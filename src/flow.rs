@@ -0,0 +1,86 @@
+//! Streamline tracing through vector fields.
+
+use crate::{
+    geometry::{Point, Rect},
+    rng::Rng,
+    shape::Shape,
+};
+
+/// Parameters for tracing particles through a vector field.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowTrace {
+    pub particles: usize,
+    /// Maximum integration steps per particle.
+    pub steps: usize,
+    /// Distance travelled per step, in pixels.
+    pub step_size: f32,
+    /// Seed for the particles' random starting positions.
+    pub seed: u64,
+}
+
+impl Default for FlowTrace {
+    fn default() -> Self {
+        Self {
+            particles: 500,
+            steps: 100,
+            step_size: 2.0,
+            seed: 0,
+        }
+    }
+}
+
+impl FlowTrace {
+    pub fn new(particles: usize, steps: usize) -> Self {
+        Self {
+            particles,
+            steps,
+            ..Self::default()
+        }
+    }
+
+    /// Traces one streamline per particle through `field`, which maps a
+    /// position to a direction vector. Only the direction is used; each step
+    /// advances `step_size` pixels with midpoint (RK2) integration.
+    ///
+    /// A line ends when it leaves `rect` or reaches a point where the field
+    /// vanishes. Lines with fewer than two points are dropped.
+    pub fn trace(&self, rect: Rect, field: impl Fn(Point) -> Point) -> Vec<Vec<Point>> {
+        let mut rng = Rng::new(self.seed);
+        let direction = |p: Point| {
+            let v = field(p);
+            let len = v.x.hypot(v.y);
+            (len > f32::EPSILON && len.is_finite()).then(|| Point::new(v.x / len, v.y / len))
+        };
+        let mut lines = Vec::with_capacity(self.particles);
+        for _ in 0..self.particles {
+            let mut p = Point::new(
+                rng.range(rect.x, rect.x + rect.width),
+                rng.range(rect.y, rect.y + rect.height),
+            );
+            let mut line = vec![p];
+            for _ in 0..self.steps {
+                let Some(d1) = direction(p) else { break };
+                let h = self.step_size / 2.0;
+                let mid = Point::new(p.x + d1.x * h, p.y + d1.y * h);
+                let Some(d2) = direction(mid) else { break };
+                p = Point::new(p.x + d2.x * self.step_size, p.y + d2.y * self.step_size);
+                if !rect.contains(p) {
+                    break;
+                }
+                line.push(p);
+            }
+            if line.len() >= 2 {
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    /// [`trace`](Self::trace) with each streamline as a polyline shape.
+    pub fn trace_shapes(&self, rect: Rect, field: impl Fn(Point) -> Point) -> Vec<Shape> {
+        self.trace(rect, field)
+            .into_iter()
+            .map(Shape::Polyline)
+            .collect()
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+
+/// A point in canvas pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn distance(self, other: Point) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+impl From<(f32, f32)> for Point {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<[f32; 2]> for Point {
+    fn from([x, y]: [f32; 2]) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.0}, {:.0}", self.x, self.y)
+    }
+}
+
+/// An axis-aligned rectangle given by its top-left corner and size.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Smallest rectangle containing all `points`.
+    pub fn from_points(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+        Some(Self::new(min.x, min.y, max.x - min.x, max.y - min.y))
+    }
+
+    pub fn union(&self, other: &Rect) -> Rect {
+        let (a, b) = (self.min(), self.max());
+        let (c, d) = (other.min(), other.max());
+        let (x, y) = (a.x.min(c.x), a.y.min(c.y));
+        Rect::new(x, y, b.x.max(d.x) - x, b.y.max(d.y) - y)
+    }
+
+    pub fn min(&self) -> Point {
+        Point::new(self.x, self.y)
+    }
+
+    pub fn max(&self) -> Point {
+        Point::new(self.x + self.width, self.y + self.height)
+    }
+
+    pub fn center(&self) -> Point {
+        Point::new(self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    pub fn contains(&self, p: impl Into<Point>) -> bool {
+        let p = p.into();
+        p.x >= self.x && p.y >= self.y && p.x <= self.x + self.width && p.y <= self.y + self.height
+    }
+
+    /// Whether the rects overlap; touching edges count.
+    pub fn intersects(&self, other: &Rect) -> bool {
+        let (a, b) = (self.min(), self.max());
+        let (c, d) = (other.min(), other.max());
+        a.x <= d.x && c.x <= b.x && a.y <= d.y && c.y <= b.y
+    }
+
+    /// The rect grown by `amount` on every side.
+    pub fn inflate(&self, amount: f32) -> Rect {
+        Rect::new(
+            self.x - amount,
+            self.y - amount,
+            self.width + 2.0 * amount,
+            self.height + 2.0 * amount,
+        )
+    }
+}
@@ -0,0 +1,141 @@
+//! Recognition of pinch and pan gestures from raw touch input.
+
+use crate::{
+    geometry::Point,
+    input::{Gesture, InputEvent, TouchEvent, TouchPhase},
+};
+
+/// Tracks active touches and turns two-finger movement into [`Gesture`]s.
+///
+/// Platform touchpad gestures arrive as [`InputEvent::Gesture`] already and
+/// pass straight through.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    touches: Vec<(u64, Point)>,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_event(&mut self, event: &InputEvent) -> Vec<Gesture> {
+        match event {
+            InputEvent::Touch(touch) => self.handle_touch(touch),
+            InputEvent::Gesture(g) => vec![*g],
+            _ => Vec::new(),
+        }
+    }
+
+    fn handle_touch(&mut self, touch: &TouchEvent) -> Vec<Gesture> {
+        match touch.phase {
+            TouchPhase::Started => {
+                self.touches.retain(|(id, _)| *id != touch.id);
+                self.touches.push((touch.id, touch.position));
+                Vec::new()
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.touches.retain(|(id, _)| *id != touch.id);
+                Vec::new()
+            }
+            TouchPhase::Moved => {
+                let before = self.pair();
+                if let Some(t) = self.touches.iter_mut().find(|(id, _)| *id == touch.id) {
+                    t.1 = touch.position;
+                }
+                match (before, self.pair()) {
+                    (Some((a0, b0)), Some((a1, b1))) => gestures_between(a0, b0, a1, b1),
+                    _ => Vec::new(),
+                }
+            }
+        }
+    }
+
+    /// The first two active touches; further fingers are ignored.
+    fn pair(&self) -> Option<(Point, Point)> {
+        match self.touches.get(..2)? {
+            [(_, a), (_, b)] => Some((*a, *b)),
+            _ => None,
+        }
+    }
+}
+
+fn gestures_between(a0: Point, b0: Point, a1: Point, b1: Point) -> Vec<Gesture> {
+    let mid = |a: Point, b: Point| Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+    let (m0, m1) = (mid(a0, b0), mid(a1, b1));
+    let mut gestures = Vec::new();
+    let (d0, d1) = (a0.distance(b0), a1.distance(b1));
+    if d0 > 0.0 && d1 != d0 {
+        gestures.push(Gesture::Pinch {
+            scale: d1 / d0,
+            center: m1,
+        });
+    }
+    if m1 != m0 {
+        gestures.push(Gesture::Pan {
+            delta: Point::new(m1.x - m0.x, m1.y - m0.y),
+        });
+    }
+    gestures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, x: f32, y: f32) -> InputEvent {
+        InputEvent::Touch(TouchEvent {
+            id,
+            phase,
+            position: Point::new(x, y),
+        })
+    }
+
+    #[test]
+    fn spreading_two_fingers_pinches() {
+        let mut recognizer = GestureRecognizer::new();
+        recognizer.handle_event(&touch(1, TouchPhase::Started, 0.0, 0.0));
+        assert!(recognizer
+            .handle_event(&touch(1, TouchPhase::Moved, 1.0, 0.0))
+            .is_empty());
+        recognizer.handle_event(&touch(2, TouchPhase::Started, 11.0, 0.0));
+        let gestures = recognizer.handle_event(&touch(2, TouchPhase::Moved, 21.0, 0.0));
+        assert_eq!(
+            gestures,
+            [
+                Gesture::Pinch {
+                    scale: 2.0,
+                    center: Point::new(11.0, 0.0),
+                },
+                Gesture::Pan {
+                    delta: Point::new(5.0, 0.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extra_fingers_are_ignored() {
+        let mut recognizer = GestureRecognizer::new();
+        recognizer.handle_event(&touch(1, TouchPhase::Started, 0.0, 0.0));
+        recognizer.handle_event(&touch(2, TouchPhase::Started, 10.0, 0.0));
+        recognizer.handle_event(&touch(3, TouchPhase::Started, 50.0, 50.0));
+        assert!(recognizer
+            .handle_event(&touch(3, TouchPhase::Moved, 60.0, 60.0))
+            .is_empty());
+        // Moving one of the first two fingers gestures about their midpoint.
+        let gestures = recognizer.handle_event(&touch(1, TouchPhase::Moved, -5.0, 0.0));
+        assert_eq!(
+            gestures,
+            [
+                Gesture::Pinch {
+                    scale: 1.5,
+                    center: Point::new(2.5, 0.0),
+                },
+                Gesture::Pan {
+                    delta: Point::new(-2.5, 0.0),
+                }
+            ]
+        );
+    }
+}
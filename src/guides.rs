@@ -0,0 +1,240 @@
+//! Pixel rulers and draggable guide lines.
+//!
+//! This module holds the state and geometry of the overlay; drawing it is left
+//! to whatever renders the canvas.
+
+use crate::geometry::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// A line parallel to the x axis, positioned by its y coordinate.
+    Horizontal,
+    /// A line parallel to the y axis, positioned by its x coordinate.
+    Vertical,
+}
+
+/// Identifies a guide for as long as it exists, even as others are removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GuideId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Guide {
+    pub orientation: Orientation,
+    pub position: f32,
+}
+
+impl Guide {
+    /// Distance from `p` to the guide line.
+    pub fn distance(&self, p: Point) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => (p.y - self.position).abs(),
+            Orientation::Vertical => (p.x - self.position).abs(),
+        }
+    }
+}
+
+/// A tick mark on a ruler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tick {
+    pub position: f32,
+    /// Major ticks are the labelled ones.
+    pub major: bool,
+}
+
+/// Ruler tick layout along one canvas edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ruler {
+    /// Distance in pixels between two minor ticks.
+    pub step: f32,
+    /// Every `major_every`-th tick is a major tick.
+    pub major_every: u32,
+}
+
+impl Default for Ruler {
+    fn default() -> Self {
+        Self {
+            step: 10.0,
+            major_every: 10,
+        }
+    }
+}
+
+impl Ruler {
+    /// Ticks covering `0..=length`.
+    pub fn ticks(&self, length: f32) -> impl Iterator<Item = Tick> + '_ {
+        let count = if self.step > 0.0 {
+            (length / self.step).floor() as u32
+        } else {
+            0
+        };
+        (0..=count).map(move |i| Tick {
+            position: i as f32 * self.step,
+            major: self.major_every != 0 && i % self.major_every == 0,
+        })
+    }
+}
+
+/// Toggleable rulers plus the set of guide lines placed on the canvas.
+#[derive(Debug, Clone, Default)]
+pub struct Guides {
+    pub show_rulers: bool,
+    pub ruler: Ruler,
+    guides: Vec<(GuideId, Guide)>,
+    next_id: u32,
+    dragging: Option<GuideId>,
+    cursor: Option<Point>,
+}
+
+impl Guides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_rulers(&mut self) {
+        self.show_rulers = !self.show_rulers;
+    }
+
+    pub fn add(&mut self, orientation: Orientation, position: f32) -> GuideId {
+        let id = GuideId(self.next_id);
+        self.next_id += 1;
+        self.guides.push((
+            id,
+            Guide {
+                orientation,
+                position,
+            },
+        ));
+        id
+    }
+
+    /// Removes a guide, ending the drag only if it was the one being dragged.
+    pub fn remove(&mut self, id: GuideId) -> Option<Guide> {
+        let index = self.guides.iter().position(|(i, _)| *i == id)?;
+        if self.dragging == Some(id) {
+            self.dragging = None;
+        }
+        Some(self.guides.remove(index).1)
+    }
+
+    pub fn clear(&mut self) {
+        self.guides.clear();
+        self.dragging = None;
+    }
+
+    pub fn get(&self, id: GuideId) -> Option<&Guide> {
+        self.guides.iter().find(|(i, _)| *i == id).map(|(_, g)| g)
+    }
+
+    pub fn get_mut(&mut self, id: GuideId) -> Option<&mut Guide> {
+        self.guides
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+            .map(|(_, g)| g)
+    }
+
+    /// All guides, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (GuideId, &Guide)> {
+        self.guides.iter().map(|(id, g)| (*id, g))
+    }
+
+    /// The guide closest to `p`, if one lies within `tolerance` pixels.
+    pub fn guide_at(&self, p: impl Into<Point>, tolerance: f32) -> Option<GuideId> {
+        let p = p.into();
+        self.iter()
+            .map(|(id, g)| (id, g.distance(p)))
+            .filter(|&(_, d)| d <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    /// Starts dragging the guide under `p`. Returns whether one was grabbed.
+    pub fn begin_drag(&mut self, p: impl Into<Point>, tolerance: f32) -> bool {
+        let p = p.into();
+        self.dragging = self.guide_at(p, tolerance);
+        self.dragging.is_some()
+    }
+
+    /// Updates the cursor position, moving the dragged guide along with it.
+    pub fn cursor_moved(&mut self, p: impl Into<Point>) {
+        let p = p.into();
+        self.cursor = Some(p);
+        if let Some(guide) = self.dragging.and_then(|id| self.get_mut(id)) {
+            guide.position = match guide.orientation {
+                Orientation::Horizontal => p.y,
+                Orientation::Vertical => p.x,
+            };
+        }
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.cursor = None;
+    }
+
+    pub fn end_drag(&mut self) {
+        self.dragging = None;
+    }
+
+    pub fn dragging(&self) -> Option<GuideId> {
+        self.dragging
+    }
+
+    /// Coordinate readout to display next to the cursor, e.g. `"120, 48"`.
+    pub fn readout(&self) -> Option<String> {
+        self.cursor.map(|p| p.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_survive_removing_other_guides() {
+        let mut guides = Guides::new();
+        let a = guides.add(Orientation::Vertical, 10.0);
+        let b = guides.add(Orientation::Horizontal, 20.0);
+        assert_eq!(guides.remove(a).map(|g| g.position), Some(10.0));
+        assert_eq!(guides.remove(a), None);
+        assert_eq!(guides.get(b).map(|g| g.position), Some(20.0));
+        let c = guides.add(Orientation::Vertical, 30.0);
+        assert_ne!(c, a);
+        assert_eq!(guides.iter().map(|(id, _)| id).collect::<Vec<_>>(), [b, c]);
+    }
+
+    #[test]
+    fn dragging_follows_the_cursor_along_one_axis() {
+        let mut guides = Guides::new();
+        let h = guides.add(Orientation::Horizontal, 20.0);
+        let v = guides.add(Orientation::Vertical, 50.0);
+        assert_eq!(guides.guide_at((48.0, 21.0), 3.0), Some(h));
+        assert!(!guides.begin_drag((0.0, 80.0), 3.0));
+
+        assert!(guides.begin_drag((52.0, 0.0), 3.0));
+        assert_eq!(guides.dragging(), Some(v));
+        guides.cursor_moved((70.0, 5.0));
+        assert_eq!(guides.get(v).unwrap().position, 70.0);
+        assert_eq!(guides.get(h).unwrap().position, 20.0);
+        assert_eq!(guides.readout().as_deref(), Some("70, 5"));
+
+        guides.end_drag();
+        guides.cursor_moved((90.0, 5.0));
+        assert_eq!(guides.get(v).unwrap().position, 70.0);
+        guides.cursor_left();
+        assert_eq!(guides.readout(), None);
+    }
+
+    #[test]
+    fn removing_another_guide_keeps_the_drag() {
+        let mut guides = Guides::new();
+        let a = guides.add(Orientation::Vertical, 10.0);
+        let b = guides.add(Orientation::Vertical, 50.0);
+        assert!(guides.begin_drag((50.0, 0.0), 1.0));
+        guides.remove(a);
+        assert_eq!(guides.dragging(), Some(b));
+        guides.cursor_moved((60.0, 0.0));
+        assert_eq!(guides.get(b).unwrap().position, 60.0);
+
+        guides.remove(b);
+        assert_eq!(guides.dragging(), None);
+    }
+}
@@ -0,0 +1,138 @@
+//! Undo / redo over snapshots of editable state.
+
+use std::{collections::VecDeque, mem};
+
+/// A bounded undo/redo history of state snapshots.
+///
+/// Call [`record`](Self::record) with a copy of the state before each
+/// undoable change (e.g. once per brush stroke), then let
+/// [`undo`](Self::undo) and [`redo`](Self::redo) swap snapshots in and out.
+#[derive(Debug, Clone)]
+pub struct History<T> {
+    undo: VecDeque<T>,
+    redo: Vec<T>,
+    depth: usize,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl<T> History<T> {
+    /// Keeps at most `depth` undo steps; older ones are discarded.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        while self.undo.len() > depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Records `previous` as the state to return to on the next undo, and
+    /// forgets everything that could have been redone.
+    pub fn record(&mut self, previous: T) {
+        self.redo.clear();
+        if self.depth == 0 {
+            return;
+        }
+        if self.undo.len() == self.depth {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(previous);
+    }
+
+    /// Restores the last recorded state into `state`. Returns false if there
+    /// is nothing to undo.
+    pub fn undo(&mut self, state: &mut T) -> bool {
+        let Some(previous) = self.undo.pop_back() else {
+            return false;
+        };
+        self.redo.push(mem::replace(state, previous));
+        true
+    }
+
+    /// Reapplies the last undone change. Returns false if there is nothing to
+    /// redo.
+    pub fn redo(&mut self, state: &mut T) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        self.undo.push_back(mem::replace(state, next));
+        if self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+        true
+    }
+
+    /// Number of snapshots held, undo and redo combined.
+    pub fn len(&self) -> usize {
+        self.undo.len() + self.redo.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redo_respects_a_lowered_depth() {
+        let mut history = History::new(10);
+        let mut state = 0;
+        for next in 1..=4 {
+            history.record(state);
+            state = next;
+        }
+        for _ in 0..3 {
+            assert!(history.undo(&mut state));
+        }
+        history.set_depth(1);
+        for _ in 0..3 {
+            assert!(history.redo(&mut state));
+        }
+        assert_eq!(state, 4);
+        assert!(history.undo(&mut state));
+        assert!(!history.undo(&mut state));
+        assert_eq!(state, 3);
+    }
+
+    #[test]
+    fn record_discards_redo() {
+        let mut history = History::new(2);
+        let mut state = 1;
+        history.record(0);
+        assert!(history.undo(&mut state));
+        assert!(history.can_redo());
+        history.record(state);
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,117 @@
+use crate::color::Color;
+
+/// An RGBA8 raster image stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// A `width` × `height` image filled with `color`.
+    pub fn new(width: u32, height: u32, color: Color) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![color.to_rgba8(); width as usize * height as usize],
+        }
+    }
+
+    /// Wraps tightly packed RGBA8 bytes. Returns `None` if `data` does not hold
+    /// exactly `width * height` pixels.
+    pub fn from_rgba8(width: u32, height: u32, data: &[u8]) -> Option<Self> {
+        if data.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+        let pixels = data
+            .chunks_exact(4)
+            .map(|c| [c[0], c[1], c[2], c[3]])
+            .collect();
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+
+    pub fn pixels_mut(&mut self) -> &mut [[u8; 4]] {
+        &mut self.pixels
+    }
+
+    pub fn as_rgba8(&self) -> &[u8] {
+        self.pixels.as_flattened()
+    }
+
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y as usize * self.width as usize + x as usize)
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> Option<Color> {
+        self.index(x, y).map(|i| Color::from_rgba8(self.pixels[i]))
+    }
+
+    /// Sets a pixel; out-of-bounds writes are ignored.
+    pub fn set(&mut self, x: u32, y: u32, color: Color) {
+        if let Some(i) = self.index(x, y) {
+            self.pixels[i] = color.to_rgba8();
+        }
+    }
+
+    /// Resamples to `width` × `height` by averaging the source area each
+    /// output pixel covers (a box filter), weighting colors by alpha.
+    pub fn resized(&self, width: u32, height: u32) -> Image {
+        let mut out = Image::new(width, height, Color::TRANSPARENT);
+        if self.width == 0 || self.height == 0 {
+            return out;
+        }
+        let sx = self.width as f32 / width as f32;
+        let sy = self.height as f32 / height as f32;
+        for y in 0..height {
+            let (y0, y1) = (y as f32 * sy, (y + 1) as f32 * sy);
+            for x in 0..width {
+                let (x0, x1) = (x as f32 * sx, (x + 1) as f32 * sx);
+                let mut acc = [0.0f32; 4];
+                let mut total = 0.0;
+                for py in y0.floor() as u32..(y1.ceil() as u32).min(self.height) {
+                    let wy = (y1.min(py as f32 + 1.0) - y0.max(py as f32)).max(0.0);
+                    for px in x0.floor() as u32..(x1.ceil() as u32).min(self.width) {
+                        let wx = (x1.min(px as f32 + 1.0) - x0.max(px as f32)).max(0.0);
+                        let w = wx * wy;
+                        let c = self.get(px, py).unwrap_or_default();
+                        acc[0] += c.r * c.a * w;
+                        acc[1] += c.g * c.a * w;
+                        acc[2] += c.b * c.a * w;
+                        acc[3] += c.a * w;
+                        total += w;
+                    }
+                }
+                if total > 0.0 && acc[3] > 0.0 {
+                    out.set(
+                        x,
+                        y,
+                        Color::rgba(
+                            acc[0] / acc[3],
+                            acc[1] / acc[3],
+                            acc[2] / acc[3],
+                            acc[3] / total,
+                        ),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,602 @@
+//! Input events in canvas pixel coordinates and callback dispatch.
+
+use std::{
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, SyncSender, TrySendError},
+    time::{Duration, Instant},
+};
+
+use crate::{geometry::Point, gesture::GestureRecognizer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Other(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseEvent {
+    Pressed {
+        button: MouseButton,
+        position: Point,
+    },
+    Released {
+        button: MouseButton,
+        position: Point,
+    },
+    Moved {
+        position: Point,
+    },
+    /// Scroll wheel or touchpad scroll, with `delta` in pixels.
+    Wheel {
+        delta: Point,
+        position: Point,
+    },
+    /// Synthesized by [`ClickDetector`] after the second of two quick
+    /// presses of the same button; follows that press's `Pressed` event.
+    DoubleClick {
+        button: MouseButton,
+        position: Point,
+    },
+}
+
+/// Counts consecutive clicks and synthesizes [`MouseEvent::DoubleClick`].
+#[derive(Debug, Clone)]
+pub struct ClickDetector {
+    /// Longest gap between presses that still counts as one multi-click.
+    pub interval: Duration,
+    /// Farthest the cursor may move between presses, in pixels.
+    pub distance: f32,
+    last: Option<(MouseButton, Instant, Point)>,
+    count: u32,
+}
+
+impl Default for ClickDetector {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), 4.0)
+    }
+}
+
+impl ClickDetector {
+    pub fn new(interval: Duration, distance: f32) -> Self {
+        Self {
+            interval,
+            distance,
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Number of clicks in the current run: 1 for a single click, 2 for a
+    /// double click, and so on.
+    pub fn click_count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn handle_event(&mut self, event: &MouseEvent) -> Option<MouseEvent> {
+        self.handle_event_at(event, Instant::now())
+    }
+
+    /// Like [`handle_event`](Self::handle_event) with an explicit timestamp.
+    pub fn handle_event_at(&mut self, event: &MouseEvent, now: Instant) -> Option<MouseEvent> {
+        let MouseEvent::Pressed { button, position } = *event else {
+            return None;
+        };
+        let continues = self.last.is_some_and(|(b, t, p)| {
+            b == button
+                && now.saturating_duration_since(t) <= self.interval
+                && p.distance(position) <= self.distance
+        });
+        self.count = if continues { self.count + 1 } else { 1 };
+        self.last = Some((button, now, position));
+        (self.count == 2).then_some(MouseEvent::DoubleClick { button, position })
+    }
+}
+
+/// Pixels scrolled per wheel line when resolving [`ScrollDelta::Lines`].
+pub const DEFAULT_LINE_HEIGHT: f32 = 20.0;
+
+/// A raw scroll amount as reported by the platform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// Wheel notches, as reported by most mice.
+    Lines { x: f32, y: f32 },
+    /// Precise pixel offsets, as reported by touchpads.
+    Pixels { x: f32, y: f32 },
+}
+
+impl ScrollDelta {
+    pub fn to_pixels(self, line_height: f32) -> Point {
+        match self {
+            ScrollDelta::Lines { x, y } => Point::new(x * line_height, y * line_height),
+            ScrollDelta::Pixels { x, y } => Point::new(x, y),
+        }
+    }
+}
+
+impl MouseEvent {
+    pub fn position(&self) -> Point {
+        match *self {
+            MouseEvent::Pressed { position, .. }
+            | MouseEvent::Released { position, .. }
+            | MouseEvent::Moved { position }
+            | MouseEvent::Wheel { position, .. }
+            | MouseEvent::DoubleClick { position, .. } => position,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PenPhase {
+    Down,
+    Moved,
+    Up,
+}
+
+/// A stylus sample, reported where the platform provides pen data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenEvent {
+    pub phase: PenPhase,
+    pub position: Point,
+    /// Normalized pressure in `0.0..=1.0`.
+    pub pressure: f32,
+    /// Tilt from vertical along x and y, in degrees (`-90.0..=90.0`), if known.
+    pub tilt: Option<(f32, f32)>,
+}
+
+/// A physical key, named after the US layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[rustfmt::skip]
+pub enum Key {
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+    Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9,
+    F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Escape, Enter, Space, Tab, Backspace, Delete,
+    Left, Right, Up, Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub pressed: bool,
+    /// Whether this is an auto-repeat of a held key.
+    pub repeat: bool,
+}
+
+/// Held modifier keys, combinable with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const CTRL: Modifiers = Modifiers(1 << 1);
+    pub const ALT: Modifiers = Modifiers(1 << 2);
+    pub const SUPER: Modifiers = Modifiers(1 << 3);
+
+    /// Whether every modifier in `other` is held.
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn shift(self) -> bool {
+        self.contains(Self::SHIFT)
+    }
+
+    pub fn ctrl(self) -> bool {
+        self.contains(Self::CTRL)
+    }
+
+    pub fn alt(self) -> bool {
+        self.contains(Self::ALT)
+    }
+
+    pub fn super_key(self) -> bool {
+        self.contains(Self::SUPER)
+    }
+}
+
+impl std::ops::BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+/// Text entry, including IME composition for CJK and similar input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextEvent {
+    /// Final text to insert.
+    Commit(String),
+    /// In-progress composition to show at the caret, replacing any previous
+    /// preedit. An empty string ends composition. `cursor` is a byte range
+    /// within `text`.
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+    ImeEnabled,
+    ImeDisabled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchPhase {
+    Started,
+    Moved,
+    Ended,
+    Cancelled,
+}
+
+/// One finger on a touchscreen, identified by `id` for the life of the touch.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchEvent {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Point,
+}
+
+/// High-level navigation gestures from touchpads and touchscreens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    /// Zoom by `scale` (relative to the previous pinch event) around `center`.
+    Pinch { scale: f32, center: Point },
+    /// Two-finger pan by `delta` pixels.
+    Pan { delta: Point },
+}
+
+/// A file dragged over or dropped onto the window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileEvent {
+    Hovered(PathBuf),
+    Dropped(PathBuf),
+    /// Files that were hovering left the window without being dropped.
+    HoverCancelled,
+}
+
+/// Changes to the window's state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowEvent {
+    /// The window gained (`true`) or lost (`false`) keyboard focus.
+    Focused(bool),
+    CursorEntered,
+    CursorLeft,
+    /// The canvas was resized to `width` × `height` pixels; re-layout and
+    /// redraw content for the new size.
+    Resized {
+        width: u32,
+        height: u32,
+    },
+    /// The app was backgrounded (e.g. on Android); stop rendering until
+    /// [`Resumed`](Self::Resumed).
+    Suspended,
+    /// The app returned to the foreground and may render again.
+    Resumed,
+    /// The user asked to close the window. See
+    /// [`InputHandlers::close_requested`] for vetoing it.
+    CloseRequested,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    Mouse(MouseEvent),
+    Pen(PenEvent),
+    Touch(TouchEvent),
+    Gesture(Gesture),
+    Key(KeyEvent),
+    ModifiersChanged(Modifiers),
+    Text(TextEvent),
+    File(FileEvent),
+    Window(WindowEvent),
+}
+
+/// Accumulates [`TextEvent`]s into the contents of a simple text box.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    /// Committed text.
+    pub text: String,
+    /// Current IME composition, shown after `text` but not yet part of it.
+    pub preedit: String,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle_event(&mut self, event: &TextEvent) {
+        match event {
+            TextEvent::Commit(s) => {
+                self.preedit.clear();
+                self.text.push_str(s);
+            }
+            TextEvent::Preedit { text, .. } => self.preedit.clone_from(text),
+            TextEvent::ImeEnabled => {}
+            TextEvent::ImeDisabled => self.preedit.clear(),
+        }
+    }
+
+    /// Text to display: committed text followed by the composition.
+    pub fn display(&self) -> String {
+        format!("{}{}", self.text, self.preedit)
+    }
+}
+
+/// The current input state, kept up to date from the event stream so it can
+/// be queried synchronously (e.g. "is shift held while dragging?").
+#[derive(Debug, Clone, Default)]
+pub struct InputState {
+    modifiers: Modifiers,
+    cursor: Option<Point>,
+    pressed: Vec<MouseButton>,
+    unfocused: bool,
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::Mouse(e) => {
+                self.cursor = Some(e.position());
+                match e {
+                    MouseEvent::Pressed { button, .. } if !self.pressed.contains(&button) => {
+                        self.pressed.push(button)
+                    }
+                    MouseEvent::Released { button, .. } => self.pressed.retain(|&b| b != button),
+                    _ => {}
+                }
+            }
+            InputEvent::Pen(e) => self.cursor = Some(e.position),
+            InputEvent::ModifiersChanged(m) => self.modifiers = m,
+            InputEvent::Touch(e) => self.cursor = Some(e.position),
+            InputEvent::Window(WindowEvent::Focused(focused)) => {
+                self.unfocused = !focused;
+                if !focused {
+                    // Releases are not reported while the window is unfocused.
+                    self.pressed.clear();
+                }
+            }
+            InputEvent::Window(WindowEvent::CursorLeft) => self.cursor = None,
+            InputEvent::Window(WindowEvent::Suspended) => {
+                // Nothing is reported while suspended, so forget held buttons.
+                self.pressed.clear();
+            }
+            InputEvent::Window(
+                WindowEvent::CursorEntered
+                | WindowEvent::Resized { .. }
+                | WindowEvent::Resumed
+                | WindowEvent::CloseRequested,
+            ) => {}
+            InputEvent::Text(_)
+            | InputEvent::Gesture(_)
+            | InputEvent::Key(_)
+            | InputEvent::File(_) => {}
+        }
+    }
+
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Last known cursor position in canvas pixels.
+    pub fn cursor(&self) -> Option<Point> {
+        self.cursor
+    }
+
+    pub fn is_pressed(&self, button: MouseButton) -> bool {
+        self.pressed.contains(&button)
+    }
+
+    /// Whether the window has keyboard focus; assumed until told otherwise.
+    pub fn is_focused(&self) -> bool {
+        !self.unfocused
+    }
+
+    /// Whether the cursor is over the window.
+    pub fn is_hovered(&self) -> bool {
+        self.cursor.is_some()
+    }
+}
+
+type Callback<E> = Box<dyn FnMut(&E) + Send>;
+
+/// User callbacks registered for input events.
+///
+/// Touch events also run through a [`GestureRecognizer`], so two-finger
+/// pinch and pan on a touchscreen reach [`on_gesture`](Self::on_gesture)
+/// handlers like touchpad gestures do.
+#[derive(Default)]
+pub struct InputHandlers {
+    mouse: Vec<Callback<MouseEvent>>,
+    pen: Vec<Callback<PenEvent>>,
+    touch: Vec<Callback<TouchEvent>>,
+    recognizer: GestureRecognizer,
+    text: Vec<Callback<TextEvent>>,
+    gesture: Vec<Callback<Gesture>>,
+    key: Vec<Callback<KeyEvent>>,
+    file: Vec<Callback<FileEvent>>,
+    window: Vec<Callback<WindowEvent>>,
+    close: Vec<Box<dyn FnMut() -> bool + Send>>,
+}
+
+impl InputHandlers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn on_mouse(&mut self, f: impl FnMut(&MouseEvent) + Send + 'static) {
+        self.mouse.push(Box::new(f));
+    }
+
+    pub fn on_pen(&mut self, f: impl FnMut(&PenEvent) + Send + 'static) {
+        self.pen.push(Box::new(f));
+    }
+
+    pub fn on_touch(&mut self, f: impl FnMut(&TouchEvent) + Send + 'static) {
+        self.touch.push(Box::new(f));
+    }
+
+    pub fn on_text(&mut self, f: impl FnMut(&TextEvent) + Send + 'static) {
+        self.text.push(Box::new(f));
+    }
+
+    pub fn on_gesture(&mut self, f: impl FnMut(&Gesture) + Send + 'static) {
+        self.gesture.push(Box::new(f));
+    }
+
+    pub fn on_key(&mut self, f: impl FnMut(&KeyEvent) + Send + 'static) {
+        self.key.push(Box::new(f));
+    }
+
+    pub fn on_file(&mut self, f: impl FnMut(&FileEvent) + Send + 'static) {
+        self.file.push(Box::new(f));
+    }
+
+    pub fn on_window(&mut self, f: impl FnMut(&WindowEvent) + Send + 'static) {
+        self.window.push(Box::new(f));
+    }
+
+    /// Registers a handler consulted before the window closes. Returning
+    /// false keeps the window open, e.g. to confirm unsaved changes;
+    /// returning true allows the close and is the place for cleanup.
+    pub fn on_close_requested(&mut self, f: impl FnMut() -> bool + Send + 'static) {
+        self.close.push(Box::new(f));
+    }
+
+    /// Asks the close handlers, in registration order, whether the window
+    /// may close. Stops at the first veto, so later handlers only run their
+    /// cleanup when the close goes ahead. The event loop exits only if this
+    /// returns true.
+    pub fn close_requested(&mut self) -> bool {
+        self.close.iter_mut().all(|f| f())
+    }
+
+    /// Calls every handler registered for `event`, in registration order.
+    pub fn dispatch(&mut self, event: &InputEvent) {
+        match event {
+            InputEvent::Mouse(e) => self.mouse.iter_mut().for_each(|f| f(e)),
+            InputEvent::Pen(e) => self.pen.iter_mut().for_each(|f| f(e)),
+            InputEvent::Text(e) => self.text.iter_mut().for_each(|f| f(e)),
+            InputEvent::Gesture(e) => self.gesture.iter_mut().for_each(|f| f(e)),
+            InputEvent::Key(e) => self.key.iter_mut().for_each(|f| f(e)),
+            InputEvent::File(e) => self.file.iter_mut().for_each(|f| f(e)),
+            InputEvent::Window(e) => self.window.iter_mut().for_each(|f| f(e)),
+            InputEvent::Touch(e) => {
+                self.touch.iter_mut().for_each(|f| f(e));
+                for gesture in self.recognizer.handle_event(event) {
+                    self.gesture.iter_mut().for_each(|f| f(&gesture));
+                }
+            }
+            InputEvent::ModifiersChanged(_) => {}
+        }
+    }
+}
+
+/// Creates a bounded queue for delivering input events to a polling consumer,
+/// as an alternative to [`InputHandlers`]. A capacity of zero is treated as
+/// one, since a queue that holds nothing would drop every event.
+pub fn event_queue(capacity: usize) -> (EventSender, EventReceiver) {
+    let (tx, rx) = mpsc::sync_channel(capacity.max(1));
+    (EventSender(tx), EventReceiver(rx))
+}
+
+/// Producer half of [`event_queue`], owned by the event loop.
+#[derive(Debug, Clone)]
+pub struct EventSender(SyncSender<InputEvent>);
+
+impl EventSender {
+    /// Queues `event` without blocking. When the queue is full the event is
+    /// dropped so a slow consumer can never stall the event loop.
+    ///
+    /// Returns false if the event was dropped or the receiver is gone.
+    pub fn send(&self, event: InputEvent) -> bool {
+        match self.0.try_send(event) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Consumer half of [`event_queue`].
+#[derive(Debug)]
+pub struct EventReceiver(Receiver<InputEvent>);
+
+impl EventReceiver {
+    /// Drains the events queued so far without blocking.
+    pub fn poll_events(&self) -> impl Iterator<Item = InputEvent> + '_ {
+        self.0.try_iter()
+    }
+
+    /// Blocks until the next event, or returns `None` once the sender is gone.
+    pub fn wait_event(&self) -> Option<InputEvent> {
+        self.0.recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    #[test]
+    fn touch_events_reach_touch_and_gesture_handlers() {
+        let touches = Arc::new(Mutex::new(0));
+        let gestures = Arc::new(Mutex::new(Vec::new()));
+        let mut handlers = InputHandlers::new();
+        let t = touches.clone();
+        handlers.on_touch(move |_| *t.lock().unwrap() += 1);
+        let g = gestures.clone();
+        handlers.on_gesture(move |gesture| g.lock().unwrap().push(*gesture));
+
+        let touch = |id, phase, x: f32| {
+            InputEvent::Touch(TouchEvent {
+                id,
+                phase,
+                position: Point::new(x, 0.0),
+            })
+        };
+        handlers.dispatch(&touch(0, TouchPhase::Started, 0.0));
+        handlers.dispatch(&touch(1, TouchPhase::Started, 10.0));
+        handlers.dispatch(&touch(1, TouchPhase::Moved, 20.0));
+
+        assert_eq!(*touches.lock().unwrap(), 3);
+        let gestures = gestures.lock().unwrap();
+        assert!(matches!(gestures[0], Gesture::Pinch { scale, .. } if scale == 2.0));
+        assert!(matches!(gestures[1], Gesture::Pan { .. }));
+    }
+
+    #[test]
+    fn close_handlers_can_veto() {
+        let mut handlers = InputHandlers::new();
+        assert!(handlers.close_requested());
+
+        let cleaned_up = Arc::new(Mutex::new(false));
+        let unsaved = Arc::new(Mutex::new(true));
+        let u = unsaved.clone();
+        handlers.on_close_requested(move || !*u.lock().unwrap());
+        let c = cleaned_up.clone();
+        handlers.on_close_requested(move || {
+            *c.lock().unwrap() = true;
+            true
+        });
+
+        assert!(!handlers.close_requested());
+        assert!(!*cleaned_up.lock().unwrap());
+        *unsaved.lock().unwrap() = false;
+        assert!(handlers.close_requested());
+        assert!(*cleaned_up.lock().unwrap());
+    }
+
+    #[test]
+    fn zero_capacity_queue_still_delivers() {
+        let (tx, rx) = event_queue(0);
+        assert!(tx.send(InputEvent::Window(WindowEvent::CursorEntered)));
+        assert_eq!(rx.poll_events().count(), 1);
+    }
+}
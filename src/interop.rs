@@ -0,0 +1,120 @@
+//! Conversions between [`Point`] and the vector types of other math crates,
+//! each behind a feature of the same name.
+
+use crate::geometry::Point;
+
+#[cfg(feature = "glam")]
+mod glam_impls {
+    use super::Point;
+
+    impl From<glam::Vec2> for Point {
+        fn from(v: glam::Vec2) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<Point> for glam::Vec2 {
+        fn from(p: Point) -> Self {
+            Self::new(p.x, p.y)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::symmetry::Symmetry;
+
+        #[test]
+        fn round_trips() {
+            let v = glam::Vec2::new(1.5, -2.0);
+            let p = Point::from(v);
+            assert_eq!(p, Point::new(1.5, -2.0));
+            assert_eq!(glam::Vec2::from(p), v);
+            assert_eq!(
+                Symmetry::Vertical.transform(1, glam::Vec2::ZERO, v),
+                Point::new(-1.5, -2.0)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "mint")]
+mod mint_impls {
+    use super::Point;
+
+    impl From<mint::Point2<f32>> for Point {
+        fn from(p: mint::Point2<f32>) -> Self {
+            Self::new(p.x, p.y)
+        }
+    }
+
+    impl From<Point> for mint::Point2<f32> {
+        fn from(p: Point) -> Self {
+            Self { x: p.x, y: p.y }
+        }
+    }
+
+    impl From<mint::Vector2<f32>> for Point {
+        fn from(v: mint::Vector2<f32>) -> Self {
+            Self::new(v.x, v.y)
+        }
+    }
+
+    impl From<Point> for mint::Vector2<f32> {
+        fn from(p: Point) -> Self {
+            Self { x: p.x, y: p.y }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::snap::Snap;
+
+        #[test]
+        fn round_trips() {
+            let p = Point::new(1.5, -2.0);
+            let point: mint::Point2<f32> = p.into();
+            let vector: mint::Vector2<f32> = p.into();
+            assert_eq!(Point::from(point), p);
+            assert_eq!(Point::from(vector), p);
+            let snap = Snap::new(10.0).unwrap().with_origin(point);
+            assert_eq!(snap.apply(vector), p);
+        }
+    }
+}
+
+#[cfg(feature = "euclid")]
+mod euclid_impls {
+    use super::Point;
+
+    impl<U> From<euclid::Point2D<f32, U>> for Point {
+        fn from(p: euclid::Point2D<f32, U>) -> Self {
+            Self::new(p.x, p.y)
+        }
+    }
+
+    impl<U> From<Point> for euclid::Point2D<f32, U> {
+        fn from(p: Point) -> Self {
+            Self::new(p.x, p.y)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::symmetry::Symmetry;
+
+        #[test]
+        fn round_trips() {
+            let e = euclid::default::Point2D::new(1.5, -2.0);
+            let p = Point::from(e);
+            assert_eq!(p, Point::new(1.5, -2.0));
+            assert_eq!(euclid::default::Point2D::from(p), e);
+            assert_eq!(
+                Symmetry::Both.replicate(e, &[Point::new(0.0, 0.0)])[3],
+                vec![Point::new(3.0, -4.0)]
+            );
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Named layers with a stacking order, visibility and per-layer compositing.
+
+use crate::color::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LayerId(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Add,
+}
+
+impl BlendMode {
+    /// Composites `src` over `dst`, with `src` alpha scaled by `opacity`.
+    pub fn blend(self, src: Color, dst: Color, opacity: f32) -> Color {
+        let sa = src.a * opacity.clamp(0.0, 1.0);
+        let mix = |s: f32, d: f32| {
+            let blended = match self {
+                BlendMode::Normal => s,
+                BlendMode::Multiply => s * d,
+                BlendMode::Screen => s + d - s * d,
+                BlendMode::Add => (s + d).min(1.0),
+            };
+            // Where the destination is transparent the source color shows as is.
+            let c = blended * dst.a + s * (1.0 - dst.a);
+            c * sa + d * dst.a * (1.0 - sa)
+        };
+        let a = sa + dst.a * (1.0 - sa);
+        if a <= 0.0 {
+            return Color::TRANSPARENT;
+        }
+        Color::rgba(
+            mix(src.r, dst.r) / a,
+            mix(src.g, dst.g) / a,
+            mix(src.b, dst.b) / a,
+            a,
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    pub blend: BlendMode,
+}
+
+/// Layers ordered bottom to top.
+#[derive(Debug, Clone, Default)]
+pub struct LayerStack {
+    layers: Vec<(LayerId, Layer)>,
+    next_id: u32,
+}
+
+impl LayerStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a visible, fully opaque layer on top of the stack.
+    pub fn add(&mut self, name: impl Into<String>) -> LayerId {
+        let id = LayerId(self.next_id);
+        self.next_id += 1;
+        self.layers.push((
+            id,
+            Layer {
+                name: name.into(),
+                visible: true,
+                opacity: 1.0,
+                blend: BlendMode::Normal,
+            },
+        ));
+        id
+    }
+
+    pub fn remove(&mut self, id: LayerId) -> Option<Layer> {
+        let index = self.z_index(id)?;
+        Some(self.layers.remove(index).1)
+    }
+
+    pub fn get(&self, id: LayerId) -> Option<&Layer> {
+        self.layers.iter().find(|(i, _)| *i == id).map(|(_, l)| l)
+    }
+
+    pub fn get_mut(&mut self, id: LayerId) -> Option<&mut Layer> {
+        self.layers
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+            .map(|(_, l)| l)
+    }
+
+    pub fn find(&self, name: &str) -> Option<LayerId> {
+        self.layers
+            .iter()
+            .find(|(_, l)| l.name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// Position in the stack, `0` being the bottom.
+    pub fn z_index(&self, id: LayerId) -> Option<usize> {
+        self.layers.iter().position(|(i, _)| *i == id)
+    }
+
+    /// Moves the layer to position `z`, clamped to the top of the stack.
+    pub fn move_to(&mut self, id: LayerId, z: usize) -> bool {
+        let Some(index) = self.z_index(id) else {
+            return false;
+        };
+        let layer = self.layers.remove(index);
+        self.layers.insert(z.min(self.layers.len()), layer);
+        true
+    }
+
+    pub fn raise(&mut self, id: LayerId) -> bool {
+        self.z_index(id).is_some_and(|z| self.move_to(id, z + 1))
+    }
+
+    pub fn lower(&mut self, id: LayerId) -> bool {
+        self.z_index(id)
+            .is_some_and(|z| self.move_to(id, z.saturating_sub(1)))
+    }
+
+    pub fn set_visible(&mut self, id: LayerId, visible: bool) {
+        if let Some(layer) = self.get_mut(id) {
+            layer.visible = visible;
+        }
+    }
+
+    pub fn set_opacity(&mut self, id: LayerId, opacity: f32) {
+        if let Some(layer) = self.get_mut(id) {
+            layer.opacity = opacity.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn set_blend(&mut self, id: LayerId, blend: BlendMode) {
+        if let Some(layer) = self.get_mut(id) {
+            layer.blend = blend;
+        }
+    }
+
+    /// All layers, bottom to top.
+    pub fn iter(&self) -> impl Iterator<Item = (LayerId, &Layer)> {
+        self.layers.iter().map(|(id, l)| (*id, l))
+    }
+
+    /// Visible layers in the order they are composited.
+    pub fn visible(&self) -> impl Iterator<Item = (LayerId, &Layer)> {
+        self.iter().filter(|(_, l)| l.visible && l.opacity > 0.0)
+    }
+
+    /// Composites one pixel from each layer (bottom to top) onto `background`.
+    pub fn composite(&self, background: Color, mut pixel: impl FnMut(LayerId) -> Color) -> Color {
+        self.visible().fold(background, |dst, (id, layer)| {
+            layer.blend.blend(pixel(id), dst, layer.opacity)
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Color, expected: Color) {
+        let close = [
+            (actual.r, expected.r),
+            (actual.g, expected.g),
+            (actual.b, expected.b),
+            (actual.a, expected.a),
+        ]
+        .iter()
+        .all(|(a, e)| (a - e).abs() < 1e-5);
+        assert!(close, "{actual:?} != {expected:?}");
+    }
+
+    #[test]
+    fn blend_modes_match_known_values() {
+        let src = Color::rgb(0.5, 0.25, 1.0);
+        let dst = Color::rgb(0.5, 0.5, 0.5);
+        let cases = [
+            (BlendMode::Normal, Color::rgb(0.5, 0.25, 1.0)),
+            (BlendMode::Multiply, Color::rgb(0.25, 0.125, 0.5)),
+            (BlendMode::Screen, Color::rgb(0.75, 0.625, 1.0)),
+            (BlendMode::Add, Color::rgb(1.0, 0.75, 1.0)),
+        ];
+        for (mode, expected) in cases {
+            assert_close(mode.blend(src, dst, 1.0), expected);
+        }
+    }
+
+    #[test]
+    fn blending_onto_transparency_keeps_the_source() {
+        let src = Color::rgba(0.5, 0.25, 1.0, 0.5);
+        for mode in [
+            BlendMode::Normal,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Add,
+        ] {
+            assert_close(mode.blend(src, Color::TRANSPARENT, 1.0), src);
+        }
+        assert_eq!(
+            BlendMode::Normal.blend(Color::TRANSPARENT, Color::TRANSPARENT, 1.0),
+            Color::TRANSPARENT
+        );
+    }
+
+    #[test]
+    fn opacity_scales_the_source() {
+        let out = BlendMode::Normal.blend(Color::WHITE, Color::BLACK, 0.25);
+        assert_close(out, Color::rgb(0.25, 0.25, 0.25));
+        let out = BlendMode::Normal.blend(Color::WHITE, Color::TRANSPARENT, 0.25);
+        assert_close(out, Color::rgba(1.0, 1.0, 1.0, 0.25));
+        assert_close(
+            BlendMode::Multiply.blend(Color::RED, Color::WHITE, 0.0),
+            Color::WHITE,
+        );
+    }
+
+    #[test]
+    fn reordering_moves_layers_within_the_stack() {
+        let mut stack = LayerStack::new();
+        let [a, b, c] = ["a", "b", "c"].map(|name| stack.add(name));
+        let order = |stack: &LayerStack| stack.iter().map(|(id, _)| id).collect::<Vec<_>>();
+
+        assert!(stack.move_to(a, 10));
+        assert_eq!(order(&stack), [b, c, a]);
+        assert!(stack.lower(a));
+        assert_eq!(order(&stack), [b, a, c]);
+        assert!(stack.raise(b));
+        assert_eq!(order(&stack), [a, b, c]);
+        assert!(stack.raise(c));
+        assert!(stack.lower(a));
+        assert_eq!(order(&stack), [a, b, c]);
+
+        stack.remove(b);
+        assert!(!stack.raise(b));
+        assert!(!stack.move_to(b, 0));
+        assert_eq!(stack.z_index(c), Some(1));
+    }
+
+    #[test]
+    fn composite_follows_the_stack_order() {
+        let mut stack = LayerStack::new();
+        let red = stack.add("red");
+        let blue = stack.add("blue");
+        let pixel = |id| if id == red { Color::RED } else { Color::BLUE };
+
+        assert_eq!(stack.composite(Color::TRANSPARENT, pixel), Color::BLUE);
+        stack.raise(red);
+        assert_eq!(stack.composite(Color::TRANSPARENT, pixel), Color::RED);
+
+        stack.set_opacity(red, 0.5);
+        assert_close(
+            stack.composite(Color::TRANSPARENT, pixel),
+            Color::rgb(0.5, 0.0, 0.5),
+        );
+        stack.set_visible(red, false);
+        stack.set_blend(blue, BlendMode::Screen);
+        assert_close(
+            stack.composite(Color::RED, pixel),
+            Color::rgb(1.0, 0.0, 1.0),
+        );
+    }
+}
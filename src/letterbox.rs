@@ -0,0 +1,53 @@
+//! Aspect-ratio preserving placement of the logical canvas inside a window.
+
+use crate::geometry::{Point, Rect};
+
+/// Fits a fixed-size logical canvas into a window of arbitrary size, scaling
+/// it uniformly and centering it with bars on the remaining sides.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Letterbox {
+    /// Region of the window the canvas is drawn into, in window pixels.
+    pub viewport: Rect,
+    /// Window pixels per canvas pixel.
+    pub scale: f32,
+}
+
+impl Letterbox {
+    pub fn new(
+        canvas_width: u32,
+        canvas_height: u32,
+        window_width: u32,
+        window_height: u32,
+    ) -> Self {
+        let (cw, ch) = (canvas_width.max(1) as f32, canvas_height.max(1) as f32);
+        let (ww, wh) = (window_width as f32, window_height as f32);
+        let scale = (ww / cw).min(wh / ch);
+        let (vw, vh) = (cw * scale, ch * scale);
+        Self {
+            viewport: Rect::new((ww - vw) / 2.0, (wh - vh) / 2.0, vw, vh),
+            scale,
+        }
+    }
+
+    /// Converts a window position (e.g. the cursor) to canvas coordinates.
+    ///
+    /// Returns `None` when the position lies on the bars outside the canvas.
+    pub fn window_to_canvas(&self, p: impl Into<Point>) -> Option<Point> {
+        let p = p.into();
+        if self.scale <= 0.0 || !self.viewport.contains(p) {
+            return None;
+        }
+        Some(Point::new(
+            (p.x - self.viewport.x) / self.scale,
+            (p.y - self.viewport.y) / self.scale,
+        ))
+    }
+
+    pub fn canvas_to_window(&self, p: impl Into<Point>) -> Point {
+        let p = p.into();
+        Point::new(
+            self.viewport.x + p.x * self.scale,
+            self.viewport.y + p.y * self.scale,
+        )
+    }
+}
@@ -0,0 +1,71 @@
+pub mod brush;
+pub mod camera;
+pub mod color;
+pub mod diagram;
+pub mod drag;
+pub mod easing;
+pub mod export;
+pub mod fill;
+pub mod flow;
+pub mod geometry;
+pub mod gesture;
+pub mod guides;
+pub mod history;
+pub mod image;
+pub mod input;
+#[cfg(any(feature = "euclid", feature = "glam", feature = "mint"))]
+mod interop;
+pub mod layers;
+pub mod letterbox;
+pub mod mask;
+pub mod palette;
+pub mod particles;
+mod rng;
+pub mod sampling;
+pub mod scene;
+pub mod shape;
+pub mod shortcuts;
+pub mod simplify;
+pub mod smoothing;
+pub mod snap;
+pub mod stats;
+pub mod symmetry;
+pub mod time;
+pub mod timer;
+pub mod trace;
+pub mod tween;
+
+pub use brush::{Brush, BrushMode, BrushStroke, Stamp, Tool};
+pub use camera::Camera;
+pub use color::Color;
+pub use diagram::Triangulation;
+pub use drag::{DragEvent, ShapeDrag};
+pub use easing::Easing;
+pub use export::ExportOptions;
+pub use flow::FlowTrace;
+pub use geometry::{Point, Rect};
+pub use gesture::GestureRecognizer;
+pub use guides::{Guide, GuideId, Guides, Orientation, Ruler, Tick};
+pub use history::History;
+pub use image::Image;
+pub use input::{
+    event_queue, ClickDetector, EventReceiver, EventSender, FileEvent, Gesture, InputEvent,
+    InputHandlers, InputState, Key, KeyEvent, Modifiers, MouseButton, MouseEvent, PenEvent,
+    PenPhase, ScrollDelta, TextEvent, TextInput, TouchEvent, TouchPhase, WindowEvent,
+};
+pub use layers::{BlendMode, Layer, LayerId, LayerStack};
+pub use letterbox::Letterbox;
+pub use mask::Mask;
+pub use palette::Palette;
+pub use particles::{Emitter, EmitterId, Particle, ParticleSystem};
+pub use scene::{FrameShapes, Item, Scene, SceneMemory, ShapeId};
+pub use shape::Shape;
+pub use shortcuts::{Action, BuiltinAction, KeyBindings};
+pub use simplify::Decimator;
+pub use smoothing::{Sample, Smoothing, Stabilizer};
+pub use snap::Snap;
+pub use stats::{FrameStats, Histogram};
+pub use symmetry::Symmetry;
+pub use time::{FixedTimestep, Frame, FrameClock, FrameLimiter};
+pub use timer::{TimerId, Timers};
+pub use tween::{Property, Tweens};
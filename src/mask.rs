@@ -0,0 +1,160 @@
+/// A binary selection mask, one flag per pixel, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    width: u32,
+    height: u32,
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    /// An empty (fully unselected) mask.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            bits: vec![false; width as usize * height as usize],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Whether `(x, y)` is selected; out-of-bounds pixels are not.
+    pub fn get(&self, x: u32, y: u32) -> bool {
+        x < self.width && y < self.height && self.bits[self.index(x, y)]
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, value: bool) {
+        if x < self.width && y < self.height {
+            let i = self.index(x, y);
+            self.bits[i] = value;
+        }
+    }
+
+    fn index(&self, x: u32, y: u32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// Number of selected pixels.
+    pub fn count(&self) -> usize {
+        self.bits.iter().filter(|&&b| b).count()
+    }
+
+    /// Coordinates of every selected pixel.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let w = self.width as usize;
+        self.bits
+            .iter()
+            .enumerate()
+            .filter(|(_, &b)| b)
+            .map(move |(i, _)| ((i % w) as u32, (i / w) as u32))
+    }
+}
+
+/// Morphological operations using a disc-shaped structuring element.
+impl Mask {
+    /// Grows the selection by `radius` pixels.
+    pub fn dilate(&self, radius: u32) -> Mask {
+        self.morph(radius, true)
+    }
+
+    /// Shrinks the selection by `radius` pixels.
+    pub fn erode(&self, radius: u32) -> Mask {
+        self.morph(radius, false)
+    }
+
+    /// Erosion followed by dilation: removes specks and thin protrusions.
+    pub fn open(&self, radius: u32) -> Mask {
+        self.erode(radius).dilate(radius)
+    }
+
+    /// Dilation followed by erosion: fills small holes and narrow gaps.
+    pub fn close(&self, radius: u32) -> Mask {
+        self.dilate(radius).erode(radius)
+    }
+
+    /// Dilation sets a pixel if any pixel within the disc is set; erosion
+    /// keeps it only if every pixel within the disc (inside the mask) is set.
+    fn morph(&self, radius: u32, dilate: bool) -> Mask {
+        let r = radius as i32;
+        let offsets: Vec<(i32, i32)> = (-r..=r)
+            .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+            .filter(|&(dx, dy)| dx * dx + dy * dy <= r * r)
+            .collect();
+        let mut out = Mask::new(self.width, self.height);
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let mut neighbors = offsets
+                    .iter()
+                    .map(|&(dx, dy)| (x + dx, y + dy))
+                    .filter(|&(nx, ny)| {
+                        nx >= 0 && ny >= 0 && nx < self.width as i32 && ny < self.height as i32
+                    })
+                    .map(|(nx, ny)| self.get(nx as u32, ny as u32));
+                let value = if dilate {
+                    neighbors.any(|b| b)
+                } else {
+                    neighbors.all(|b| b)
+                };
+                out.set(x as u32, y as u32, value);
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(size: u32, x0: u32, y0: u32, side: u32) -> Mask {
+        let mut mask = Mask::new(size, size);
+        for y in y0..y0 + side {
+            for x in x0..x0 + side {
+                mask.set(x, y, true);
+            }
+        }
+        mask
+    }
+
+    #[test]
+    fn dilating_a_pixel_gives_a_disc() {
+        let mask = square(9, 4, 4, 1);
+        assert_eq!(mask.dilate(1).count(), 5);
+        assert_eq!(mask.dilate(2).count(), 13);
+        assert_eq!(mask.dilate(0), mask);
+    }
+
+    #[test]
+    fn erosion_undoes_dilation_of_a_square() {
+        let mask = square(12, 4, 4, 4);
+        assert_eq!(mask.erode(1).count(), 4);
+        assert_eq!(mask.dilate(1).erode(1), mask);
+    }
+
+    #[test]
+    fn open_removes_specks_and_close_fills_holes() {
+        let mut specks = square(12, 2, 2, 6);
+        specks.set(10, 10, true);
+        // Opening also rounds the square's corners, so compare against the
+        // square opened on its own.
+        assert_eq!(specks.open(1), square(12, 2, 2, 6).open(1));
+        assert!(!specks.open(1).get(10, 10));
+
+        let mut holed = square(12, 2, 2, 6);
+        holed.set(4, 4, false);
+        assert_eq!(holed.close(1), square(12, 2, 2, 6));
+    }
+
+    #[test]
+    fn operations_stay_within_bounds() {
+        let full = square(4, 0, 0, 4);
+        assert_eq!(full.erode(1), full);
+        assert_eq!(full.dilate(3), full);
+    }
+}
@@ -0,0 +1,158 @@
+//! Color palettes, including extraction of dominant colors from an image.
+
+use crate::{color::Color, image::Image};
+
+/// Upper bound on the pixels fed to k-means; larger images are subsampled.
+const MAX_SAMPLES: usize = 16_384;
+const MAX_ITERATIONS: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Palette {
+    pub colors: Vec<Color>,
+}
+
+impl Palette {
+    pub fn new(colors: Vec<Color>) -> Self {
+        Self { colors }
+    }
+
+    /// Extracts up to `k` dominant colors with k-means clustering in RGB space.
+    ///
+    /// Fully transparent pixels are ignored. Colors are ordered from the most
+    /// to the least common; fewer than `k` are returned if the image does not
+    /// have that many distinct colors.
+    pub fn from_image(image: &Image, k: usize) -> Self {
+        let opaque: Vec<[f32; 3]> = image
+            .pixels()
+            .iter()
+            .filter(|p| p[3] > 0)
+            .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+            .collect();
+        let stride = opaque.len().div_ceil(MAX_SAMPLES).max(1);
+        let samples: Vec<[f32; 3]> = opaque.into_iter().step_by(stride).collect();
+        if samples.is_empty() || k == 0 {
+            return Self::default();
+        }
+
+        let mut centroids = initial_centroids(&samples, k);
+        let mut assignment = vec![0; samples.len()];
+        for _ in 0..MAX_ITERATIONS {
+            let mut changed = false;
+            for (a, s) in assignment.iter_mut().zip(&samples) {
+                let nearest = nearest(&centroids, s);
+                changed |= *a != nearest;
+                *a = nearest;
+            }
+            let mut sums = vec![([0.0f32; 3], 0usize); centroids.len()];
+            for (&a, s) in assignment.iter().zip(&samples) {
+                for (sum, v) in sums[a].0.iter_mut().zip(s) {
+                    *sum += v;
+                }
+                sums[a].1 += 1;
+            }
+            for (centroid, (sum, n)) in centroids.iter_mut().zip(&sums) {
+                if *n > 0 {
+                    *centroid = sum.map(|v| v / *n as f32);
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let mut counts = vec![0usize; centroids.len()];
+        for &a in &assignment {
+            counts[a] += 1;
+        }
+        let mut clusters: Vec<_> = centroids
+            .into_iter()
+            .zip(counts)
+            .filter(|&(_, n)| n > 0)
+            .collect();
+        clusters.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+        Self::new(
+            clusters
+                .into_iter()
+                .map(|(c, _)| Color::rgb(c[0] / 255.0, c[1] / 255.0, c[2] / 255.0))
+                .collect(),
+        )
+    }
+
+    /// The palette entry closest to `color` in RGB space.
+    pub fn nearest(&self, color: Color) -> Option<Color> {
+        let target = [color.r, color.g, color.b];
+        self.colors.iter().copied().min_by(|a, b| {
+            distance2(&[a.r, a.g, a.b], &target).total_cmp(&distance2(&[b.r, b.g, b.b], &target))
+        })
+    }
+}
+
+fn distance2(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum()
+}
+
+fn nearest(centroids: &[[f32; 3]], s: &[f32; 3]) -> usize {
+    centroids
+        .iter()
+        .enumerate()
+        .min_by(|a, b| distance2(a.1, s).total_cmp(&distance2(b.1, s)))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Deterministic farthest-point seeding: start from the first sample and
+/// repeatedly add the sample farthest from every centroid chosen so far.
+fn initial_centroids(samples: &[[f32; 3]], k: usize) -> Vec<[f32; 3]> {
+    let mut centroids = vec![samples[0]];
+    let mut dist: Vec<f32> = samples.iter().map(|s| distance2(s, &samples[0])).collect();
+    while centroids.len() < k {
+        let (i, &d) = dist
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .expect("samples is not empty");
+        if d == 0.0 {
+            break;
+        }
+        let c = samples[i];
+        centroids.push(c);
+        for (dd, s) in dist.iter_mut().zip(samples) {
+            *dd = dd.min(distance2(s, &c));
+        }
+    }
+    centroids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_dominant_colors_by_frequency() {
+        let mut image = Image::new(8, 8, Color::RED);
+        for y in 0..2 {
+            for x in 0..8 {
+                image.set(x, y, Color::BLUE);
+            }
+        }
+        image.set(0, 7, Color::TRANSPARENT);
+        let palette = Palette::from_image(&image, 2);
+        assert_eq!(palette.colors, vec![Color::RED, Color::BLUE]);
+    }
+
+    #[test]
+    fn returns_fewer_colors_than_requested_when_the_image_has_fewer() {
+        let image = Image::new(4, 4, Color::GREEN);
+        assert_eq!(Palette::from_image(&image, 5).colors, vec![Color::GREEN]);
+        assert!(Palette::from_image(&image, 0).colors.is_empty());
+    }
+
+    #[test]
+    fn nearest_picks_the_closest_entry() {
+        let palette = Palette::new(vec![Color::BLACK, Color::WHITE]);
+        assert_eq!(
+            palette.nearest(Color::rgb(0.8, 0.7, 0.9)),
+            Some(Color::WHITE)
+        );
+        assert_eq!(Palette::default().nearest(Color::WHITE), None);
+    }
+}
@@ -0,0 +1,276 @@
+//! A CPU particle system: emitters spawning particles that move, age and fade.
+//!
+//! Advance it once per frame with [`ParticleSystem::update`] and draw the
+//! particles as points or small quads.
+
+use std::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use crate::{color::Color, geometry::Point, rng::Rng, shape::Shape};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub position: Point,
+    /// Pixels per second.
+    pub velocity: Point,
+    pub age: f32,
+    pub lifetime: f32,
+    pub size: f32,
+    pub color: Color,
+}
+
+impl Particle {
+    /// Fraction of the lifetime used up, `0.0..=1.0`.
+    pub fn life(&self) -> f32 {
+        (self.age / self.lifetime).clamp(0.0, 1.0)
+    }
+
+    pub fn to_shape(&self) -> Shape {
+        Shape::Circle {
+            center: self.position,
+            radius: self.size / 2.0,
+        }
+    }
+}
+
+/// Spawn parameters for particles from one source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Emitter {
+    pub position: Point,
+    /// Particles spawned per second.
+    pub rate: f32,
+    /// Lifetime range in seconds.
+    pub lifetime: (f32, f32),
+    /// Initial speed range in pixels per second.
+    pub speed: (f32, f32),
+    /// Emission direction in radians (0 is +x, y points down) and the spread
+    /// around it; a spread of `TAU` emits in every direction.
+    pub direction: f32,
+    pub spread: f32,
+    pub size: f32,
+    /// Colors interpolated evenly over a particle's life.
+    pub color_over_life: Vec<Color>,
+    /// Constant acceleration, e.g. gravity, in pixels per second squared.
+    pub acceleration: Point,
+    pub enabled: bool,
+}
+
+impl Default for Emitter {
+    fn default() -> Self {
+        Self {
+            position: Point::default(),
+            rate: 50.0,
+            lifetime: (1.0, 2.0),
+            speed: (20.0, 60.0),
+            direction: 0.0,
+            spread: TAU,
+            size: 3.0,
+            color_over_life: vec![Color::WHITE, Color::WHITE.with_alpha(0.0)],
+            acceleration: Point::default(),
+            enabled: true,
+        }
+    }
+}
+
+impl Emitter {
+    pub fn new(position: impl Into<Point>) -> Self {
+        Self {
+            position: position.into(),
+            ..Self::default()
+        }
+    }
+
+    fn color_at(&self, life: f32) -> Color {
+        let stops = &self.color_over_life;
+        match stops.len() {
+            0 => Color::WHITE,
+            1 => stops[0],
+            n => {
+                let x = life * (n - 1) as f32;
+                let i = (x.floor() as usize).min(n - 2);
+                let t = x - i as f32;
+                let (a, b) = (stops[i], stops[i + 1]);
+                Color::rgba(
+                    a.r + (b.r - a.r) * t,
+                    a.g + (b.g - a.g) * t,
+                    a.b + (b.b - a.b) * t,
+                    a.a + (b.a - a.a) * t,
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EmitterId {
+    system: u64,
+    index: usize,
+}
+
+/// Source of the ids that tie an [`EmitterId`] to the system that issued it.
+static NEXT_SYSTEM: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone)]
+pub struct ParticleSystem {
+    emitters: Vec<(Emitter, f32)>,
+    /// Each live particle with the emitter it came from.
+    particles: Vec<(Particle, usize)>,
+    /// Live particles beyond this count are not spawned.
+    pub max_particles: usize,
+    rng: Rng,
+    id: u64,
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl ParticleSystem {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            emitters: Vec::new(),
+            particles: Vec::new(),
+            max_particles: 100_000,
+            rng: Rng::new(seed),
+            id: NEXT_SYSTEM.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn add_emitter(&mut self, emitter: Emitter) -> EmitterId {
+        self.emitters.push((emitter, 0.0));
+        EmitterId {
+            system: self.id,
+            index: self.emitters.len() - 1,
+        }
+    }
+
+    /// The emitter's index, if `id` was issued by this system.
+    fn index(&self, id: EmitterId) -> Option<usize> {
+        (id.system == self.id && id.index < self.emitters.len()).then_some(id.index)
+    }
+
+    /// `None` if `id` belongs to another system.
+    pub fn emitter_mut(&mut self, id: EmitterId) -> Option<&mut Emitter> {
+        let index = self.index(id)?;
+        Some(&mut self.emitters[index].0)
+    }
+
+    /// Spawns `count` particles from an emitter at once, regardless of rate.
+    /// Returns false if `id` belongs to another system.
+    pub fn burst(&mut self, id: EmitterId, count: usize) -> bool {
+        let Some(index) = self.index(id) else {
+            return false;
+        };
+        for _ in 0..count {
+            self.spawn(index);
+        }
+        true
+    }
+
+    fn spawn(&mut self, emitter: usize) {
+        if self.particles.len() >= self.max_particles {
+            return;
+        }
+        let e = &self.emitters[emitter].0;
+        let rng = &mut self.rng;
+        let angle = e.direction + rng.range(-e.spread / 2.0, e.spread / 2.0);
+        let speed = rng.range(e.speed.0, e.speed.1);
+        let particle = Particle {
+            position: e.position,
+            velocity: Point::new(angle.cos() * speed, angle.sin() * speed),
+            age: 0.0,
+            lifetime: rng.range(e.lifetime.0, e.lifetime.1).max(f32::EPSILON),
+            size: e.size,
+            color: e.color_at(0.0),
+        };
+        self.particles.push((particle, emitter));
+    }
+
+    /// Advances the simulation by `delta`: spawns new particles, integrates
+    /// motion, updates colors and removes expired particles.
+    pub fn update(&mut self, delta: Duration) {
+        let dt = delta.as_secs_f32();
+        for i in 0..self.emitters.len() {
+            let (emitter, carry) = &mut self.emitters[i];
+            if !emitter.enabled {
+                continue;
+            }
+            *carry += emitter.rate * dt;
+            let count = carry.floor();
+            *carry -= count;
+            for _ in 0..count as usize {
+                self.spawn(i);
+            }
+        }
+
+        let emitters = &self.emitters;
+        self.particles.retain_mut(|(p, emitter)| {
+            p.age += dt;
+            if p.age >= p.lifetime {
+                return false;
+            }
+            let e = &emitters[*emitter].0;
+            p.velocity.x += e.acceleration.x * dt;
+            p.velocity.y += e.acceleration.y * dt;
+            p.position.x += p.velocity.x * dt;
+            p.position.y += p.velocity.y * dt;
+            p.color = e.color_at(p.life());
+            true
+        });
+    }
+
+    pub fn particles(&self) -> impl Iterator<Item = &Particle> {
+        self.particles.iter().map(|(p, _)| p)
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst_ignores_emitters_from_another_system() {
+        let mut other = ParticleSystem::new(1);
+        let foreign = other.add_emitter(Emitter::default());
+
+        let mut system = ParticleSystem::new(1);
+        let id = system.add_emitter(Emitter::default());
+        assert!(!system.burst(foreign, 10));
+        assert!(system.emitter_mut(foreign).is_none());
+        assert!(system.is_empty());
+        assert!(system.burst(id, 10));
+        assert_eq!(system.len(), 10);
+    }
+
+    #[test]
+    fn particles_expire_after_their_lifetime() {
+        let mut system = ParticleSystem::new(7);
+        let id = system.add_emitter(Emitter {
+            rate: 0.0,
+            lifetime: (0.5, 1.0),
+            ..Emitter::new((0.0, 0.0))
+        });
+        system.burst(id, 100);
+        system.update(Duration::from_millis(400));
+        assert_eq!(system.len(), 100);
+        system.update(Duration::from_millis(700));
+        assert!(system.is_empty());
+    }
+}
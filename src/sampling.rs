@@ -0,0 +1,175 @@
+//! Point sampling patterns for generative layouts.
+//!
+//! Every random pattern takes a seed so sketches are reproducible.
+
+use std::f32::consts::{PI, TAU};
+
+use crate::{
+    geometry::{Point, Rect},
+    rng::Rng,
+};
+
+/// Attempts per active sample before it is retired (Bridson's `k`).
+const POISSON_ATTEMPTS: usize = 30;
+
+/// Poisson-disk samples in `rect`: no two points are closer than `min_dist`.
+pub fn poisson_disk(rect: Rect, min_dist: f32, seed: u64) -> Vec<Point> {
+    poisson_disk_where(rect, min_dist, seed, |_| true)
+}
+
+/// Poisson-disk samples restricted to the part of `rect` where `inside`
+/// returns true, e.g. a circle or polygon.
+pub fn poisson_disk_where(
+    rect: Rect,
+    min_dist: f32,
+    seed: u64,
+    inside: impl Fn(Point) -> bool,
+) -> Vec<Point> {
+    if !min_dist.is_finite() || min_dist <= 0.0 || rect.width <= 0.0 || rect.height <= 0.0 {
+        return Vec::new();
+    }
+    let mut rng = Rng::new(seed);
+    let cell = min_dist / 2f32.sqrt();
+    let cols = (rect.width / cell).ceil() as usize;
+    let rows = (rect.height / cell).ceil() as usize;
+    let mut grid: Vec<Option<usize>> = vec![None; cols * rows];
+    let cell_of = |p: Point| {
+        let cx = (((p.x - rect.x) / cell) as usize).min(cols - 1);
+        let cy = (((p.y - rect.y) / cell) as usize).min(rows - 1);
+        (cx, cy)
+    };
+
+    let mut points = Vec::new();
+    let mut active = Vec::new();
+
+    // Seed with the first random point that satisfies `inside`.
+    for _ in 0..POISSON_ATTEMPTS * 10 {
+        let p = Point::new(
+            rng.range(rect.x, rect.x + rect.width),
+            rng.range(rect.y, rect.y + rect.height),
+        );
+        if inside(p) {
+            let (cx, cy) = cell_of(p);
+            grid[cy * cols + cx] = Some(0);
+            points.push(p);
+            active.push(0);
+            break;
+        }
+    }
+
+    while !active.is_empty() {
+        let slot = rng.below(active.len());
+        let origin = points[active[slot]];
+        let mut found = false;
+        for _ in 0..POISSON_ATTEMPTS {
+            let angle = rng.range(0.0, TAU);
+            let radius = rng.range(min_dist, 2.0 * min_dist);
+            let p = Point::new(
+                origin.x + radius * angle.cos(),
+                origin.y + radius * angle.sin(),
+            );
+            if !rect.contains(p) || !inside(p) {
+                continue;
+            }
+            let (cx, cy) = cell_of(p);
+            let far_enough = (cy.saturating_sub(2)..(cy + 3).min(rows))
+                .flat_map(|y| (cx.saturating_sub(2)..(cx + 3).min(cols)).map(move |x| (x, y)))
+                .filter_map(|(x, y)| grid[y * cols + x])
+                .all(|i| points[i].distance(p) >= min_dist);
+            if far_enough {
+                grid[cy * cols + cx] = Some(points.len());
+                active.push(points.len());
+                points.push(p);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(slot);
+        }
+    }
+    points
+}
+
+/// One point per cell of a `cols` × `rows` grid over `rect`, displaced from the
+/// cell center by a random amount. `jitter` in `0.0..=1.0` scales the
+/// displacement: `0.0` keeps every point centered and `1.0` places it anywhere
+/// in its cell.
+pub fn jittered_grid(rect: Rect, cols: u32, rows: u32, jitter: f32, seed: u64) -> Vec<Point> {
+    let mut rng = Rng::new(seed);
+    let (cw, ch) = (rect.width / cols as f32, rect.height / rows as f32);
+    let jitter = jitter.clamp(0.0, 1.0) / 2.0;
+    let mut points = Vec::with_capacity(cols as usize * rows as usize);
+    for row in 0..rows {
+        for col in 0..cols {
+            points.push(Point::new(
+                rect.x + cw * (col as f32 + 0.5 + rng.range(-jitter, jitter)),
+                rect.y + ch * (row as f32 + 0.5 + rng.range(-jitter, jitter)),
+            ));
+        }
+    }
+    points
+}
+
+/// `n` points on a golden-angle (sunflower) spiral filling a disc of `radius`.
+pub fn golden_spiral(center: impl Into<Point>, n: usize, radius: f32) -> Vec<Point> {
+    let center = center.into();
+    let golden_angle = PI * (3.0 - 5f32.sqrt());
+    (0..n)
+        .map(|i| {
+            let r = radius * ((i as f32 + 0.5) / n as f32).sqrt();
+            let theta = i as f32 * golden_angle;
+            Point::new(center.x + r * theta.cos(), center.y + r * theta.sin())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisson_disk_respects_min_distance_and_bounds() {
+        let rect = Rect::new(10.0, 20.0, 200.0, 100.0);
+        let points = poisson_disk(rect, 8.0, 1);
+        assert!(points.len() > 100);
+        assert!(points.iter().all(|&p| rect.contains(p)));
+        for (i, a) in points.iter().enumerate() {
+            for b in &points[i + 1..] {
+                assert!(a.distance(*b) >= 8.0);
+            }
+        }
+    }
+
+    #[test]
+    fn poisson_disk_is_reproducible_and_honors_the_region() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        assert_eq!(poisson_disk(rect, 5.0, 3), poisson_disk(rect, 5.0, 3));
+        let center = Point::new(50.0, 50.0);
+        let disc = poisson_disk_where(rect, 5.0, 3, |p| p.distance(center) <= 30.0);
+        assert!(!disc.is_empty());
+        assert!(disc.iter().all(|p| p.distance(center) <= 30.0));
+        assert!(poisson_disk(rect, 0.0, 3).is_empty());
+    }
+
+    #[test]
+    fn jittered_grid_keeps_one_point_per_cell() {
+        let rect = Rect::new(0.0, 0.0, 40.0, 30.0);
+        let points = jittered_grid(rect, 4, 3, 1.0, 9);
+        assert_eq!(points.len(), 12);
+        for (i, p) in points.iter().enumerate() {
+            let (col, row) = ((i % 4) as f32, (i / 4) as f32);
+            assert!(p.x >= col * 10.0 && p.x <= (col + 1.0) * 10.0);
+            assert!(p.y >= row * 10.0 && p.y <= (row + 1.0) * 10.0);
+        }
+        let centered = jittered_grid(rect, 4, 3, 0.0, 9);
+        assert_eq!(centered[5], Point::new(15.0, 15.0));
+    }
+
+    #[test]
+    fn golden_spiral_fills_the_disc() {
+        let points = golden_spiral((0.0, 0.0), 200, 10.0);
+        assert_eq!(points.len(), 200);
+        assert!(points.iter().all(|p| p.distance(Point::default()) <= 10.0));
+    }
+}
@@ -0,0 +1,369 @@
+//! Retained-mode shapes addressed by handles.
+//!
+//! Instead of resubmitting everything each frame, shapes are added once and
+//! then updated or removed individually through their [`ShapeId`].
+
+use std::{collections::BTreeMap, mem};
+
+use crate::{
+    color::Color,
+    geometry::{Point, Rect},
+    shape::Shape,
+};
+
+/// Handle to a shape in a [`Scene`]. Ids are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShapeId(u64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub shape: Shape,
+    pub color: Color,
+}
+
+/// Host memory held by a [`Scene`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SceneMemory {
+    pub shapes: usize,
+    /// Points stored in polylines and polygons.
+    pub points: usize,
+    /// Approximate bytes used, ignoring allocator overhead.
+    pub bytes: usize,
+}
+
+/// A retained set of shapes, drawn in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct Scene {
+    // Ids increase monotonically, so key order is draw order.
+    items: BTreeMap<ShapeId, Item>,
+    next_id: u64,
+    max_shapes: Option<usize>,
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a shape on top. When the scene is at its
+    /// [`max_shapes`](Self::max_shapes) limit the oldest shapes are evicted
+    /// to make room.
+    pub fn add(&mut self, shape: Shape, color: Color) -> ShapeId {
+        let id = ShapeId(self.next_id);
+        self.next_id += 1;
+        self.items.insert(id, Item { shape, color });
+        self.evict();
+        id
+    }
+
+    /// Caps the number of shapes kept, turning the scene into a ring buffer
+    /// for long-running sketches. `None` removes the cap. Shapes over a new
+    /// cap are evicted immediately, oldest first. A cap of zero is treated as
+    /// one, so the id returned by [`add`](Self::add) is always live.
+    pub fn set_max_shapes(&mut self, max: Option<usize>) {
+        self.max_shapes = max.map(|max| max.max(1));
+        self.evict();
+    }
+
+    pub fn max_shapes(&self) -> Option<usize> {
+        self.max_shapes
+    }
+
+    fn evict(&mut self) {
+        if let Some(max) = self.max_shapes {
+            while self.items.len() > max {
+                self.items.pop_first();
+            }
+        }
+    }
+
+    pub fn add_point(&mut self, p: impl Into<Point>, color: Color) -> ShapeId {
+        self.add(Shape::Point(p.into()), color)
+    }
+
+    pub fn add_line(&mut self, a: impl Into<Point>, b: impl Into<Point>, color: Color) -> ShapeId {
+        self.add(Shape::Line(a.into(), b.into()), color)
+    }
+
+    pub fn add_rect(&mut self, rect: Rect, color: Color) -> ShapeId {
+        self.add(Shape::Rect(rect), color)
+    }
+
+    pub fn add_circle(&mut self, center: impl Into<Point>, radius: f32, color: Color) -> ShapeId {
+        self.add(
+            Shape::Circle {
+                center: center.into(),
+                radius,
+            },
+            color,
+        )
+    }
+
+    /// Replaces a shape's geometry, keeping its id and draw position.
+    /// Returns false if `id` is not in the scene.
+    pub fn update_shape(&mut self, id: ShapeId, shape: Shape) -> bool {
+        self.items
+            .get_mut(&id)
+            .map(|item| item.shape = shape)
+            .is_some()
+    }
+
+    pub fn set_color(&mut self, id: ShapeId, color: Color) -> bool {
+        self.items
+            .get_mut(&id)
+            .map(|item| item.color = color)
+            .is_some()
+    }
+
+    pub fn remove_shape(&mut self, id: ShapeId) -> Option<Item> {
+        self.items.remove(&id)
+    }
+
+    pub fn get(&self, id: ShapeId) -> Option<&Item> {
+        self.items.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: ShapeId) -> Option<&mut Item> {
+        self.items.get_mut(&id)
+    }
+
+    pub fn contains(&self, id: ShapeId) -> bool {
+        self.items.contains_key(&id)
+    }
+
+    /// Shapes in draw order (bottom first).
+    pub fn iter(&self) -> impl Iterator<Item = (ShapeId, &Item)> {
+        self.items.iter().map(|(id, item)| (*id, item))
+    }
+
+    /// Shapes that may be visible in `view`, in draw order. `margin` grows
+    /// each shape's bounds to account for stroke width or point size. Pass
+    /// [`Camera::visible_rect`](crate::Camera::visible_rect) to cull
+    /// everything off screen.
+    pub fn visible(&self, view: Rect, margin: f32) -> impl Iterator<Item = (ShapeId, &Item)> {
+        let view = view.inflate(margin);
+        self.iter().filter(move |(_, item)| {
+            item.shape
+                .bounds()
+                .is_some_and(|bounds| bounds.intersects(&view))
+        })
+    }
+
+    /// Shapes containing `p`, topmost first.
+    pub fn hit_test(&self, p: impl Into<Point>, tolerance: f32) -> Vec<ShapeId> {
+        let p = p.into();
+        self.items
+            .iter()
+            .rev()
+            .filter(|(_, item)| item.shape.contains(p, tolerance))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Removes shapes hidden entirely under a later opaque filled rect, such
+    /// as everything beneath a full-canvas fill, and returns how many were
+    /// removed. `margin` grows each shape's bounds to account for stroke
+    /// width or point size.
+    pub fn remove_occluded(&mut self, margin: f32) -> usize {
+        let mut occluders: Vec<Rect> = Vec::new();
+        let mut hidden = Vec::new();
+        for (id, item) in self.items.iter().rev() {
+            let covered = item.shape.bounds().is_some_and(|bounds| {
+                let bounds = bounds.inflate(margin);
+                occluders
+                    .iter()
+                    .any(|o| o.contains(bounds.min()) && o.contains(bounds.max()))
+            });
+            if covered {
+                hidden.push(*id);
+            } else if let Shape::Rect(rect) = item.shape {
+                if item.color.a >= 1.0 {
+                    occluders.push(rect);
+                }
+            }
+        }
+        for id in &hidden {
+            self.items.remove(id);
+        }
+        hidden.len()
+    }
+
+    /// How much memory the scene holds, so long-running applications can
+    /// monitor growth and react, e.g. with [`set_max_shapes`](Self::set_max_shapes).
+    pub fn memory_stats(&self) -> SceneMemory {
+        // Points are counted by length; bytes include the spare capacity
+        // actually allocated.
+        let (points, allocated) = self
+            .items
+            .values()
+            .map(|item| match &item.shape {
+                Shape::Polyline(points) | Shape::Polygon(points) => {
+                    (points.len(), points.capacity())
+                }
+                _ => (0, 0),
+            })
+            .fold((0, 0), |(n, c), (len, cap)| (n + len, c + cap));
+        let shapes = self.items.len();
+        SceneMemory {
+            shapes,
+            points,
+            bytes: shapes * mem::size_of::<(ShapeId, Item)>() + allocated * mem::size_of::<Point>(),
+        }
+    }
+
+    /// Bounds of every shape in the scene.
+    pub fn content_bounds(&self) -> Option<Rect> {
+        crate::shape::content_bounds(self.items.values().map(|item| &item.shape))
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// Immediate-mode shapes rebuilt every frame.
+///
+/// Shapes pushed between [`begin_frame`](Self::begin_frame) and
+/// [`end_frame`](Self::end_frame) replace the previous frame's shapes all at
+/// once, so a renderer reading [`shapes`](Self::shapes) never sees a
+/// half-built frame.
+#[derive(Debug, Clone, Default)]
+pub struct FrameShapes {
+    front: Vec<Item>,
+    back: Vec<Item>,
+    building: bool,
+}
+
+impl FrameShapes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts building a new frame, discarding any unfinished one.
+    pub fn begin_frame(&mut self) {
+        self.back.clear();
+        self.building = true;
+    }
+
+    /// Adds a shape to the frame being built. Outside a frame the shape is
+    /// added to the current frame directly.
+    pub fn push(&mut self, shape: Shape, color: Color) {
+        let target = if self.building {
+            &mut self.back
+        } else {
+            &mut self.front
+        };
+        target.push(Item { shape, color });
+    }
+
+    /// Publishes the frame built since [`begin_frame`](Self::begin_frame).
+    pub fn end_frame(&mut self) {
+        if self.building {
+            std::mem::swap(&mut self.front, &mut self.back);
+            self.building = false;
+        }
+    }
+
+    pub fn is_building(&self) -> bool {
+        self.building
+    }
+
+    /// The last completed frame, in draw order.
+    pub fn shapes(&self) -> &[Item] {
+        &self.front
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_shapes_evicts_oldest_first() {
+        let mut scene = Scene::new();
+        let ids: Vec<_> = (0..5)
+            .map(|i| scene.add_point((i as f32, 0.0), Color::BLACK))
+            .collect();
+        scene.set_max_shapes(Some(3));
+        assert_eq!(scene.len(), 3);
+        assert!(!scene.contains(ids[1]));
+        assert!(scene.contains(ids[2]));
+
+        scene.set_max_shapes(Some(0));
+        assert_eq!(scene.max_shapes(), Some(1));
+        let id = scene.add_point((9.0, 9.0), Color::BLACK);
+        assert!(scene.contains(id));
+        assert_eq!(scene.len(), 1);
+    }
+
+    #[test]
+    fn memory_stats_count_stored_points() {
+        let mut scene = Scene::new();
+        let mut points = Vec::with_capacity(100);
+        points.extend([Point::new(0.0, 0.0), Point::new(1.0, 1.0)]);
+        scene.add(Shape::Polyline(points), Color::BLACK);
+        scene.add_circle((0.0, 0.0), 1.0, Color::BLACK);
+        let stats = scene.memory_stats();
+        assert_eq!(stats.shapes, 2);
+        assert_eq!(stats.points, 2);
+        assert!(stats.bytes >= 100 * mem::size_of::<Point>());
+    }
+
+    #[test]
+    fn visible_culls_with_margin() {
+        let mut scene = Scene::new();
+        let inside = scene.add_circle((50.0, 50.0), 5.0, Color::BLACK);
+        let near = scene.add_point((103.0, 50.0), Color::BLACK);
+        let far = scene.add_line((200.0, 0.0), (300.0, 0.0), Color::BLACK);
+        let view = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let ids = |margin| {
+            scene
+                .visible(view, margin)
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(0.0), [inside]);
+        assert_eq!(ids(5.0), [inside, near]);
+        assert_eq!(ids(100.0), [inside, near, far]);
+    }
+
+    #[test]
+    fn only_later_opaque_rects_occlude() {
+        let mut scene = Scene::new();
+        let below = scene.add_circle((50.0, 50.0), 5.0, Color::BLACK);
+        let edge = scene.add_point((99.5, 50.0), Color::BLACK);
+        let translucent = scene.add_rect(
+            Rect::new(10.0, 10.0, 50.0, 50.0),
+            Color::rgba(1.0, 1.0, 1.0, 0.5),
+        );
+        let hidden = scene.add_line((10.0, 10.0), (20.0, 20.0), Color::BLACK);
+        let fill = scene.add_rect(Rect::new(0.0, 0.0, 100.0, 100.0), Color::WHITE);
+        let above = scene.add_circle((50.0, 50.0), 5.0, Color::BLACK);
+
+        // With a margin the point near the edge pokes out of the fill.
+        assert_eq!(scene.remove_occluded(1.0), 3);
+        for id in [below, translucent, hidden] {
+            assert!(!scene.contains(id));
+        }
+        for id in [edge, fill, above] {
+            assert!(scene.contains(id));
+        }
+        assert_eq!(scene.remove_occluded(0.0), 1);
+        assert!(!scene.contains(edge));
+
+        // A translucent rect on top hides nothing.
+        scene.add_rect(
+            Rect::new(0.0, 0.0, 100.0, 100.0),
+            Color::rgba(0.0, 0.0, 0.0, 0.99),
+        );
+        assert_eq!(scene.remove_occluded(0.0), 0);
+    }
+}
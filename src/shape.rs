@@ -0,0 +1,130 @@
+//! Drawable primitives and their geometry.
+
+use crate::geometry::{Point, Rect};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    Point(Point),
+    Line(Point, Point),
+    Polyline(Vec<Point>),
+    /// A closed, filled polygon.
+    Polygon(Vec<Point>),
+    Rect(Rect),
+    Circle {
+        center: Point,
+        radius: f32,
+    },
+}
+
+impl Shape {
+    /// Axis-aligned bounds of the shape, `None` for an empty point list.
+    pub fn bounds(&self) -> Option<Rect> {
+        match self {
+            Shape::Point(p) => Some(Rect::new(p.x, p.y, 0.0, 0.0)),
+            Shape::Line(a, b) => Rect::from_points([*a, *b]),
+            Shape::Polyline(points) | Shape::Polygon(points) => {
+                Rect::from_points(points.iter().copied())
+            }
+            Shape::Rect(r) => Some(*r),
+            Shape::Circle { center, radius } => Some(Rect::new(
+                center.x - radius,
+                center.y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            )),
+        }
+    }
+
+    /// Moves the shape by `(dx, dy)`.
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        let shift = |p: &mut Point| {
+            p.x += dx;
+            p.y += dy;
+        };
+        match self {
+            Shape::Point(p) => shift(p),
+            Shape::Line(a, b) => {
+                shift(a);
+                shift(b);
+            }
+            Shape::Polyline(points) | Shape::Polygon(points) => points.iter_mut().for_each(shift),
+            Shape::Rect(r) => {
+                r.x += dx;
+                r.y += dy;
+            }
+            Shape::Circle { center, .. } => shift(center),
+        }
+    }
+
+    /// Whether `p` lies on the shape: inside filled shapes, or within
+    /// `tolerance` pixels of points and lines.
+    pub fn contains(&self, p: impl Into<Point>, tolerance: f32) -> bool {
+        let p = p.into();
+        match self {
+            Shape::Point(q) => q.distance(p) <= tolerance,
+            Shape::Line(a, b) => segment_distance(p, *a, *b) <= tolerance,
+            Shape::Polyline(points) => {
+                points
+                    .windows(2)
+                    .any(|w| segment_distance(p, w[0], w[1]) <= tolerance)
+                    || points.len() == 1 && points[0].distance(p) <= tolerance
+            }
+            Shape::Polygon(points) => {
+                polygon_contains(points, p)
+                    || (0..points.len()).any(|i| {
+                        let b = points[(i + 1) % points.len()];
+                        segment_distance(p, points[i], b) <= tolerance
+                    })
+            }
+            Shape::Rect(r) => Rect::new(
+                r.x - tolerance,
+                r.y - tolerance,
+                r.width + 2.0 * tolerance,
+                r.height + 2.0 * tolerance,
+            )
+            .contains(p),
+            Shape::Circle { center, radius } => center.distance(p) <= radius + tolerance,
+        }
+    }
+}
+
+/// Indices of the shapes containing `p`, topmost (last drawn) first.
+pub fn hit_test(shapes: &[Shape], p: impl Into<Point>, tolerance: f32) -> Vec<usize> {
+    let p = p.into();
+    (0..shapes.len())
+        .rev()
+        .filter(|&i| shapes[i].contains(p, tolerance))
+        .collect()
+}
+
+pub(crate) fn segment_distance(p: Point, a: Point, b: Point) -> f32 {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let len2 = dx * dx + dy * dy;
+    if len2 == 0.0 {
+        return a.distance(p);
+    }
+    let t = (((p.x - a.x) * dx + (p.y - a.y) * dy) / len2).clamp(0.0, 1.0);
+    Point::new(a.x + t * dx, a.y + t * dy).distance(p)
+}
+
+/// Even-odd point-in-polygon test.
+fn polygon_contains(points: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    let mut j = points.len().wrapping_sub(1);
+    for (i, a) in points.iter().enumerate() {
+        let b = points[j];
+        if (a.y > p.y) != (b.y > p.y) && p.x < (b.x - a.x) * (p.y - a.y) / (b.y - a.y) + a.x {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+/// Union of the bounds of all `shapes`, `None` when nothing has extent.
+pub fn content_bounds<'a>(shapes: impl IntoIterator<Item = &'a Shape>) -> Option<Rect> {
+    shapes
+        .into_iter()
+        .filter_map(Shape::bounds)
+        .reduce(|a, b| a.union(&b))
+}
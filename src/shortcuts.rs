@@ -0,0 +1,104 @@
+//! User-definable keyboard shortcuts.
+
+use std::fmt;
+
+use crate::input::{InputEvent, InputState, Key, Modifiers};
+
+/// Actions with a built-in meaning, carried out by whoever owns the canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinAction {
+    SaveScreenshot,
+    Clear,
+    Undo,
+    Redo,
+}
+
+pub enum Action {
+    Builtin(BuiltinAction),
+    Callback(Box<dyn FnMut() + Send>),
+}
+
+impl fmt::Debug for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Action::Builtin(a) => f.debug_tuple("Builtin").field(a).finish(),
+            Action::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+impl From<BuiltinAction> for Action {
+    fn from(action: BuiltinAction) -> Self {
+        Action::Builtin(action)
+    }
+}
+
+/// A table of key + modifier combinations and the actions they trigger.
+#[derive(Debug, Default)]
+pub struct KeyBindings {
+    bindings: Vec<(Key, Modifiers, Action)>,
+    state: InputState,
+}
+
+impl KeyBindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Common defaults: Ctrl+S screenshot, Ctrl+Z undo, Ctrl+Shift+Z and
+    /// Ctrl+Y redo, Delete clear.
+    pub fn with_defaults() -> Self {
+        let mut bindings = Self::new();
+        bindings.bind(Key::S, Modifiers::CTRL, BuiltinAction::SaveScreenshot);
+        bindings.bind(Key::Z, Modifiers::CTRL, BuiltinAction::Undo);
+        bindings.bind(
+            Key::Z,
+            Modifiers::CTRL | Modifiers::SHIFT,
+            BuiltinAction::Redo,
+        );
+        bindings.bind(Key::Y, Modifiers::CTRL, BuiltinAction::Redo);
+        bindings.bind(Key::Delete, Modifiers::NONE, BuiltinAction::Clear);
+        bindings
+    }
+
+    /// Binds `key` pressed with exactly `modifiers` held, replacing any
+    /// previous binding of the same combination.
+    pub fn bind(&mut self, key: Key, modifiers: Modifiers, action: impl Into<Action>) {
+        self.unbind(key, modifiers);
+        self.bindings.push((key, modifiers, action.into()));
+    }
+
+    /// Binds a user callback.
+    pub fn bind_fn(&mut self, key: Key, modifiers: Modifiers, f: impl FnMut() + Send + 'static) {
+        self.bind(key, modifiers, Action::Callback(Box::new(f)));
+    }
+
+    pub fn unbind(&mut self, key: Key, modifiers: Modifiers) {
+        self.bindings
+            .retain(|(k, m, _)| (*k, *m) != (key, modifiers));
+    }
+
+    /// Feeds an input event. Callbacks run immediately; a triggered built-in
+    /// action is returned for the caller to perform. Key repeats are ignored.
+    pub fn handle_event(&mut self, event: &InputEvent) -> Option<BuiltinAction> {
+        self.state.update(event);
+        let InputEvent::Key(key) = event else {
+            return None;
+        };
+        if !key.pressed || key.repeat {
+            return None;
+        }
+        let modifiers = self.state.modifiers();
+        let (_, _, action) = self
+            .bindings
+            .iter_mut()
+            .find(|(k, m, _)| *k == key.key && *m == modifiers)?;
+        match action {
+            Action::Builtin(a) => Some(*a),
+            Action::Callback(f) => {
+                f();
+                None
+            }
+        }
+    }
+}
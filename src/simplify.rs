@@ -0,0 +1,171 @@
+//! Reducing the number of points in dense point streams and polylines.
+
+use crate::{camera::Camera, geometry::Point, shape::segment_distance};
+
+/// Drops points closer than `min_distance` to the last kept point, merging
+/// repeated and sub-pixel samples. The first and last points are always
+/// kept.
+pub fn decimate(points: &[Point], min_distance: f32) -> Vec<Point> {
+    let mut decimator = Decimator::new(min_distance);
+    let mut kept: Vec<Point> = points
+        .iter()
+        .copied()
+        .filter(|p| decimator.accept(*p))
+        .collect();
+    let Some(&last) = points.last() else {
+        return kept;
+    };
+    if kept.last() != Some(&last) {
+        // Replace the last kept point rather than keep one too close to it.
+        if kept.len() > 1 {
+            kept.pop();
+        }
+        kept.push(last);
+    }
+    kept
+}
+
+/// Ramer–Douglas–Peucker simplification: drops points while keeping every
+/// dropped point within `tolerance` of the result. The first and last points
+/// are always kept.
+pub fn simplify(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // An explicit stack, since recursion depth can reach the point count.
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let (a, b) = (points[first], points[last]);
+        let farthest = (first + 1..last)
+            .map(|i| (i, segment_distance(points[i], a, b)))
+            .max_by(|x, y| x.1.total_cmp(&y.1));
+        if let Some((i, distance)) = farthest {
+            if distance > tolerance {
+                keep[i] = true;
+                stack.push((first, i));
+                stack.push((i, last));
+            }
+        }
+    }
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| keep.then_some(*p))
+        .collect()
+}
+
+/// Level-of-detail simplification for the current zoom: drops detail
+/// smaller than `pixels` screen pixels, so zoomed-out views of huge
+/// polylines draw far fewer points.
+pub fn simplify_for_camera(points: &[Point], camera: &Camera, pixels: f32) -> Vec<Point> {
+    simplify(points, pixels / camera.zoom.max(f32::EPSILON))
+}
+
+/// Streaming form of [`decimate`] for pointer samples arriving one at a time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Decimator {
+    pub min_distance: f32,
+    last: Option<Point>,
+}
+
+impl Decimator {
+    pub fn new(min_distance: f32) -> Self {
+        Self {
+            min_distance,
+            last: None,
+        }
+    }
+
+    /// Whether `p` is far enough from the last accepted point to keep.
+    pub fn accept(&mut self, p: impl Into<Point>) -> bool {
+        let p = p.into();
+        match self.last {
+            Some(last) if last.distance(p) < self.min_distance || last == p => false,
+            _ => {
+                self.last = Some(p);
+                true
+            }
+        }
+    }
+
+    /// Starts a new stream; the next point is always accepted.
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wave(n: usize) -> Vec<Point> {
+        (0..n)
+            .map(|i| {
+                let x = i as f32 * 0.1;
+                Point::new(x, x.sin() * 10.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn simplify_keeps_endpoints_and_stays_within_tolerance() {
+        let points = wave(1000);
+        for tolerance in [0.01, 0.5, 5.0] {
+            let simplified = simplify(&points, tolerance);
+            assert_eq!(simplified.first(), points.first());
+            assert_eq!(simplified.last(), points.last());
+            assert!(simplified.len() < points.len());
+            for p in &points {
+                let distance = simplified
+                    .windows(2)
+                    .map(|w| segment_distance(*p, w[0], w[1]))
+                    .fold(f32::INFINITY, f32::min);
+                assert!(distance <= tolerance + 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn simplify_collapses_straight_lines() {
+        let line: Vec<Point> = (0..100)
+            .map(|i| Point::new(i as f32, 2.0 * i as f32))
+            .collect();
+        assert_eq!(simplify(&line, 0.01), vec![line[0], line[99]]);
+        assert_eq!(simplify(&line[..2], 0.01), line[..2].to_vec());
+    }
+
+    #[test]
+    fn camera_zoom_scales_the_tolerance() {
+        let points = wave(1000);
+        let mut camera = Camera::new(800, 600);
+        let near = simplify_for_camera(&points, &camera, 0.5).len();
+        camera.zoom = 0.05;
+        let far = simplify_for_camera(&points, &camera, 0.5).len();
+        assert!(far < near);
+    }
+
+    #[test]
+    fn decimate_merges_close_points_but_keeps_endpoints() {
+        let points = [(0.0, 0.0), (0.0, 0.0), (0.2, 0.0), (5.0, 0.0), (5.1, 0.0)].map(Point::from);
+        assert_eq!(
+            decimate(&points, 1.0),
+            vec![Point::new(0.0, 0.0), Point::new(5.1, 0.0)]
+        );
+        assert_eq!(decimate(&points[..1], 1.0), vec![Point::new(0.0, 0.0)]);
+        assert!(decimate(&[], 1.0).is_empty());
+    }
+
+    #[test]
+    fn decimator_drops_repeats_until_reset() {
+        let mut decimator = Decimator::new(0.5);
+        assert!(decimator.accept((1.0, 1.0)));
+        assert!(!decimator.accept((1.0, 1.0)));
+        assert!(!decimator.accept((1.2, 1.0)));
+        assert!(decimator.accept((2.0, 1.0)));
+        decimator.reset();
+        assert!(decimator.accept((2.0, 1.0)));
+    }
+}
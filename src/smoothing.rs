@@ -0,0 +1,211 @@
+//! Stabilization of raw pointer positions before they become strokes.
+//!
+//! A [`Stabilizer`] takes pointer samples one at a time and emits smoothed
+//! samples, so hand jitter does not show up in freehand lines.
+
+use std::collections::VecDeque;
+
+use crate::geometry::Point;
+
+/// How pointer positions are smoothed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Smoothing {
+    /// Samples pass through unchanged.
+    #[default]
+    None,
+    /// Each output is the average of the last `n` samples. Larger windows
+    /// are smoother but trail further behind the pointer.
+    MovingAverage(usize),
+    /// A Catmull-Rom spline through the samples, with `segments` points
+    /// between consecutive samples. Rounds off corners of sparse input
+    /// at the cost of one sample of latency.
+    CatmullRom { segments: u32 },
+}
+
+/// A pointer position with its pressure.
+pub type Sample = (Point, f32);
+
+#[derive(Debug, Clone, Default)]
+pub struct Stabilizer {
+    smoothing: Smoothing,
+    window: VecDeque<Sample>,
+}
+
+impl Stabilizer {
+    pub fn new(smoothing: Smoothing) -> Self {
+        Self {
+            smoothing,
+            window: VecDeque::new(),
+        }
+    }
+
+    pub fn smoothing(&self) -> Smoothing {
+        self.smoothing
+    }
+
+    /// Forgets the current stroke.
+    pub fn reset(&mut self) {
+        self.window.clear();
+    }
+
+    /// Feeds a sample and returns the smoothed samples now available. The
+    /// first sample of a stroke is always returned unchanged.
+    pub fn push(&mut self, position: impl Into<Point>, pressure: f32) -> Vec<Sample> {
+        let sample = (position.into(), pressure);
+        match self.smoothing {
+            Smoothing::None => vec![sample],
+            Smoothing::MovingAverage(n) => {
+                self.window.push_back(sample);
+                while self.window.len() > n.max(1) {
+                    self.window.pop_front();
+                }
+                vec![self.average()]
+            }
+            Smoothing::CatmullRom { segments } => {
+                if self.window.is_empty() {
+                    // Duplicate the first point so the spline starts there.
+                    self.window.extend([sample, sample]);
+                    return vec![sample];
+                }
+                self.window.push_back(sample);
+                if self.window.len() < 4 {
+                    return Vec::new();
+                }
+                let out = self.segment(segments);
+                self.window.pop_front();
+                out
+            }
+        }
+    }
+
+    /// Ends the stroke, returning the samples that bring it up to the last
+    /// pointer position.
+    pub fn finish(&mut self) -> Vec<Sample> {
+        let mut out = Vec::new();
+        match self.smoothing {
+            Smoothing::None => {}
+            Smoothing::MovingAverage(_) => {
+                while self.window.len() > 1 {
+                    self.window.pop_front();
+                    out.push(self.average());
+                }
+            }
+            Smoothing::CatmullRom { segments } => {
+                if self.window.len() >= 3 {
+                    let last = self.window[self.window.len() - 1];
+                    self.window.push_back(last);
+                    out = self.segment(segments);
+                }
+            }
+        }
+        self.window.clear();
+        out
+    }
+
+    fn average(&self) -> Sample {
+        let n = self.window.len() as f32;
+        let (x, y, pressure) = self
+            .window
+            .iter()
+            .fold((0.0, 0.0, 0.0), |(x, y, p), (point, pressure)| {
+                (x + point.x, y + point.y, p + pressure)
+            });
+        (Point::new(x / n, y / n), pressure / n)
+    }
+
+    /// Points on the spline from `window[1]` to `window[2]`, excluding the
+    /// start, which was emitted with the previous segment.
+    fn segment(&self, segments: u32) -> Vec<Sample> {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| self.window[i]);
+        let segments = segments.max(1);
+        (1..=segments)
+            .map(|i| {
+                let t = i as f32 / segments as f32;
+                let point = Point::new(
+                    catmull_rom(a.0.x, b.0.x, c.0.x, d.0.x, t),
+                    catmull_rom(a.0.y, b.0.y, c.0.y, d.0.y, t),
+                );
+                (point, b.1 + (c.1 - b.1) * t)
+            })
+            .collect()
+    }
+}
+
+/// Uniform Catmull-Rom interpolation between `p1` and `p2`.
+fn catmull_rom(p0: f32, p1: f32, p2: f32, p3: f32, t: f32) -> f32 {
+    let (t2, t3) = (t * t, t * t * t);
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(smoothing: Smoothing, input: &[Sample]) -> Vec<Sample> {
+        let mut stabilizer = Stabilizer::new(smoothing);
+        let mut out: Vec<Sample> = input
+            .iter()
+            .flat_map(|&(p, pressure)| stabilizer.push(p, pressure))
+            .collect();
+        out.extend(stabilizer.finish());
+        out
+    }
+
+    fn zigzag() -> Vec<Sample> {
+        (0..10)
+            .map(|i| {
+                let y = if i % 2 == 0 { 1.0 } else { -1.0 };
+                (Point::new(i as f32 * 4.0, y), i as f32 / 9.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn none_passes_samples_through() {
+        let input = zigzag();
+        assert_eq!(run(Smoothing::None, &input), input);
+    }
+
+    #[test]
+    fn strokes_start_and_end_at_the_pointer() {
+        let input = zigzag();
+        for smoothing in [
+            Smoothing::MovingAverage(4),
+            Smoothing::CatmullRom { segments: 5 },
+        ] {
+            let out = run(smoothing, &input);
+            assert_eq!(out.first(), input.first(), "{smoothing:?}");
+            let (last, pressure) = *out.last().unwrap();
+            assert!(last.distance(input[9].0) < 1e-4, "{smoothing:?}");
+            assert!((pressure - 1.0).abs() < 1e-4, "{smoothing:?}");
+        }
+    }
+
+    #[test]
+    fn moving_average_damps_jitter() {
+        let out = run(Smoothing::MovingAverage(4), &zigzag());
+        // Past the warm-up, averages of alternating ±1 stay near zero.
+        assert!(out[4..9].iter().all(|(p, _)| p.y.abs() <= 0.5));
+    }
+
+    #[test]
+    fn catmull_rom_passes_through_every_sample() {
+        let input = zigzag();
+        let out = run(Smoothing::CatmullRom { segments: 4 }, &input);
+        assert_eq!(out.len(), 1 + 9 * 4);
+        for (i, (p, _)) in input.iter().enumerate() {
+            assert!(out[i * 4].0.distance(*p) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn catmull_rom_keeps_straight_lines_straight() {
+        let input: Vec<Sample> = (0..5).map(|i| (Point::new(i as f32, 0.0), 1.0)).collect();
+        let out = run(Smoothing::CatmullRom { segments: 3 }, &input);
+        assert!(out.iter().all(|(p, _)| p.y == 0.0));
+        assert!(out.windows(2).all(|w| w[0].0.x < w[1].0.x));
+    }
+}
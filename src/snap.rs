@@ -0,0 +1,43 @@
+//! Snap-to-grid quantization of submitted coordinates.
+
+use crate::geometry::Point;
+
+/// A square grid that coordinates are snapped to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Snap {
+    pub spacing: f32,
+    pub origin: Point,
+}
+
+impl Snap {
+    /// A grid with the given spacing anchored at the canvas origin.
+    ///
+    /// Returns `None` for a non-positive or non-finite spacing, which callers
+    /// treat as snapping disabled.
+    pub fn new(spacing: f32) -> Option<Self> {
+        (spacing.is_finite() && spacing > 0.0).then_some(Self {
+            spacing,
+            origin: Point::default(),
+        })
+    }
+
+    pub fn with_origin(self, origin: impl Into<Point>) -> Self {
+        Self {
+            origin: origin.into(),
+            ..self
+        }
+    }
+
+    /// Nearest grid intersection to `p`.
+    pub fn apply(&self, p: impl Into<Point>) -> Point {
+        let p = p.into();
+        let snap = |v: f32, o: f32| o + ((v - o) / self.spacing).round() * self.spacing;
+        Point::new(snap(p.x, self.origin.x), snap(p.y, self.origin.y))
+    }
+
+    pub fn apply_all(&self, points: &mut [Point]) {
+        for p in points {
+            *p = self.apply(*p);
+        }
+    }
+}
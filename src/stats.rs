@@ -0,0 +1,212 @@
+//! Frame pacing statistics: present intervals, jitter and a latency histogram.
+
+use std::{
+    collections::VecDeque,
+    io::{self, Write},
+    time::{Duration, Instant},
+};
+
+/// Records the interval between consecutive presents over a sliding window.
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    capacity: usize,
+    intervals: VecDeque<Duration>,
+    /// Start time and duration of each recorded frame, for trace export.
+    frames: VecDeque<(Duration, Duration)>,
+    origin: Option<Instant>,
+    last_present: Option<Instant>,
+}
+
+/// Counts of frame intervals falling into fixed-width buckets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram {
+    pub bucket_width: Duration,
+    /// `buckets[i]` counts intervals in `i * width .. (i + 1) * width`; the
+    /// last bucket also holds everything longer.
+    pub buckets: Vec<u32>,
+}
+
+impl Default for FrameStats {
+    fn default() -> Self {
+        Self::new(600)
+    }
+}
+
+impl FrameStats {
+    /// Keeps the most recent `capacity` frames.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            intervals: VecDeque::new(),
+            frames: VecDeque::new(),
+            origin: None,
+            last_present: None,
+        }
+    }
+
+    /// Call once per presented frame.
+    pub fn record_present(&mut self) {
+        self.record_present_at(Instant::now());
+    }
+
+    pub fn record_present_at(&mut self, now: Instant) {
+        let origin = *self.origin.get_or_insert(now);
+        if let Some(last) = self.last_present.replace(now) {
+            let interval = now.saturating_duration_since(last);
+            if self.intervals.len() == self.capacity {
+                self.intervals.pop_front();
+                self.frames.pop_front();
+            }
+            self.intervals.push_back(interval);
+            self.frames
+                .push_back((last.saturating_duration_since(origin), interval));
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.capacity);
+    }
+
+    pub fn intervals(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn mean_interval(&self) -> Option<Duration> {
+        let n = self.intervals.len() as u32;
+        (n > 0).then(|| self.intervals.iter().sum::<Duration>() / n)
+    }
+
+    pub fn fps(&self) -> Option<f64> {
+        self.mean_interval()
+            .filter(|d| !d.is_zero())
+            .map(|d| 1.0 / d.as_secs_f64())
+    }
+
+    /// Standard deviation of the present interval.
+    pub fn jitter(&self) -> Option<Duration> {
+        let mean = self.mean_interval()?.as_secs_f64();
+        let variance = self
+            .intervals
+            .iter()
+            .map(|d| (d.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / self.intervals.len() as f64;
+        Some(Duration::from_secs_f64(variance.sqrt()))
+    }
+
+    /// The interval below which fraction `p` (in `0.0..=1.0`) of frames fall.
+    pub fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut sorted: Vec<_> = self.intervals.iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_unstable();
+        let rank = (p.clamp(0.0, 1.0) * (sorted.len() - 1) as f64).round() as usize;
+        Some(sorted[rank])
+    }
+
+    /// Buckets the recorded intervals. A zero `bucket_width` puts every
+    /// interval in the last bucket.
+    pub fn histogram(&self, bucket_width: Duration, bucket_count: usize) -> Histogram {
+        let mut buckets = vec![0; bucket_count.max(1)];
+        let last = buckets.len() - 1;
+        for d in &self.intervals {
+            let i = if bucket_width.is_zero() {
+                last
+            } else {
+                ((d.as_nanos() / bucket_width.as_nanos()) as usize).min(last)
+            };
+            buckets[i] += 1;
+        }
+        Histogram {
+            bucket_width,
+            buckets,
+        }
+    }
+
+    /// Writes the recorded frames in the Chrome trace event format, viewable in
+    /// `chrome://tracing` or Perfetto.
+    pub fn write_chrome_trace(&self, mut w: impl Write) -> io::Result<()> {
+        write!(w, "{{\"traceEvents\":[")?;
+        for (i, (start, dur)) in self.frames.iter().enumerate() {
+            if i > 0 {
+                write!(w, ",")?;
+            }
+            write!(
+                w,
+                "{{\"name\":\"frame\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\"ts\":{},\"dur\":{}}}",
+                start.as_micros(),
+                dur.as_micros()
+            )?;
+        }
+        writeln!(w, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    /// Stats for presents at the given offsets (in milliseconds) from a fixed
+    /// start.
+    fn presents(capacity: usize, offsets: &[u64]) -> FrameStats {
+        let start = Instant::now();
+        let mut stats = FrameStats::new(capacity);
+        for &offset in offsets {
+            stats.record_present_at(start + ms(offset));
+        }
+        stats
+    }
+
+    #[test]
+    fn summary_statistics() {
+        let stats = presents(10, &[0, 10, 30, 60, 100]);
+        assert_eq!(stats.mean_interval(), Some(ms(25)));
+        assert_eq!(stats.fps(), Some(40.0));
+        let jitter = stats.jitter().unwrap().as_secs_f64();
+        assert!((jitter - 125e-6f64.sqrt()).abs() < 1e-9);
+
+        let empty = FrameStats::new(10);
+        assert_eq!(empty.mean_interval(), None);
+        assert_eq!(empty.jitter(), None);
+        assert_eq!(empty.percentile(0.5), None);
+    }
+
+    #[test]
+    fn percentiles_pick_the_nearest_rank() {
+        let stats = presents(10, &[0, 40, 50, 80, 100]);
+        assert_eq!(stats.percentile(0.0), Some(ms(10)));
+        assert_eq!(stats.percentile(0.5), Some(ms(30)));
+        assert_eq!(stats.percentile(1.0), Some(ms(40)));
+        assert_eq!(stats.percentile(7.0), Some(ms(40)));
+    }
+
+    #[test]
+    fn histogram_overflows_into_the_last_bucket() {
+        let stats = presents(10, &[0, 10, 30, 60, 100]);
+        let histogram = stats.histogram(ms(10), 3);
+        assert_eq!(histogram.buckets, [0, 1, 3]);
+        assert_eq!(stats.histogram(Duration::ZERO, 3).buckets, [0, 0, 4]);
+        assert_eq!(stats.histogram(ms(10), 0).buckets, [4]);
+    }
+
+    #[test]
+    fn the_window_keeps_the_latest_frames() {
+        let stats = presents(2, &[0, 10, 30, 60]);
+        assert_eq!(stats.intervals().collect::<Vec<_>>(), [ms(20), ms(30)]);
+
+        let mut trace = Vec::new();
+        stats.write_chrome_trace(&mut trace).unwrap();
+        assert_eq!(
+            String::from_utf8(trace).unwrap(),
+            "{\"traceEvents\":[\
+             {\"name\":\"frame\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\"ts\":10000,\"dur\":20000},\
+             {\"name\":\"frame\",\"ph\":\"X\",\"pid\":0,\"tid\":0,\"ts\":30000,\"dur\":30000}\
+             ]}\n"
+        );
+    }
+}
@@ -0,0 +1,68 @@
+//! Symmetry / mirror drawing: replicating geometry around a center point.
+
+use std::f32::consts::TAU;
+
+use crate::geometry::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Symmetry {
+    #[default]
+    None,
+    /// Mirror across the vertical axis through the center (left/right).
+    Vertical,
+    /// Mirror across the horizontal axis through the center (top/bottom).
+    Horizontal,
+    /// Mirror across both axes, producing four copies.
+    Both,
+    /// `n`-fold rotational symmetry around the center.
+    Radial(u32),
+}
+
+impl Symmetry {
+    /// Number of copies a primitive is replicated into, including the original.
+    pub fn copies(&self) -> usize {
+        match *self {
+            Symmetry::None => 1,
+            Symmetry::Vertical | Symmetry::Horizontal => 2,
+            Symmetry::Both => 4,
+            Symmetry::Radial(n) => n.max(1) as usize,
+        }
+    }
+
+    /// Maps `p` to its `index`-th image, `0` being the identity.
+    pub fn transform(&self, index: usize, center: impl Into<Point>, p: impl Into<Point>) -> Point {
+        let (center, p) = (center.into(), p.into());
+        let mirror_x = |p: Point| Point::new(2.0 * center.x - p.x, p.y);
+        let mirror_y = |p: Point| Point::new(p.x, 2.0 * center.y - p.y);
+        match (*self, index) {
+            (_, 0) => p,
+            (Symmetry::Vertical, _) => mirror_x(p),
+            (Symmetry::Horizontal, _) => mirror_y(p),
+            (Symmetry::Both, 1) => mirror_x(p),
+            (Symmetry::Both, 2) => mirror_y(p),
+            (Symmetry::Both, _) => mirror_x(mirror_y(p)),
+            (Symmetry::Radial(n), i) => {
+                let (sin, cos) = (TAU * i as f32 / n.max(1) as f32).sin_cos();
+                let (dx, dy) = (p.x - center.x, p.y - center.y);
+                Point::new(
+                    center.x + dx * cos - dy * sin,
+                    center.y + dx * sin + dy * cos,
+                )
+            }
+            (Symmetry::None, _) => p,
+        }
+    }
+
+    /// Every copy of the primitive given by `points`, the original first.
+    pub fn replicate(&self, center: impl Into<Point>, points: &[Point]) -> Vec<Vec<Point>> {
+        let center = center.into();
+        (0..self.copies())
+            .map(|i| {
+                points
+                    .iter()
+                    .map(|&p| self.transform(i, center, p))
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,244 @@
+//! Frame timing for animation.
+
+use std::time::{Duration, Instant};
+
+/// Timing information for one animation frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    /// Zero-based frame number.
+    pub index: u64,
+    /// Time since the first frame.
+    pub elapsed: Duration,
+    /// Time since the previous frame; zero for the first frame.
+    pub delta: Duration,
+}
+
+impl Frame {
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+}
+
+/// Produces a [`Frame`] per tick, measured from the first tick.
+#[derive(Debug, Clone, Default)]
+pub struct FrameClock {
+    start: Option<Instant>,
+    last: Option<Instant>,
+    last_delta: Duration,
+    next_index: u64,
+}
+
+impl FrameClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new frame now.
+    pub fn tick(&mut self) -> Frame {
+        self.tick_at(Instant::now())
+    }
+
+    /// Starts a new frame at `now`, e.g. the presentation time reported by
+    /// the display.
+    pub fn tick_at(&mut self, now: Instant) -> Frame {
+        let start = *self.start.get_or_insert(now);
+        let delta = self
+            .last
+            .replace(now)
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+        let frame = Frame {
+            index: self.next_index,
+            elapsed: now.saturating_duration_since(start),
+            delta,
+        };
+        self.last_delta = delta;
+        self.next_index += 1;
+        frame
+    }
+
+    /// Time from the first tick to the latest one.
+    pub fn elapsed(&self) -> Duration {
+        match (self.start, self.last) {
+            (Some(start), Some(last)) => last.saturating_duration_since(start),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Number of frames ticked so far.
+    pub fn frame_count(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Delta of the latest frame; zero before the second tick.
+    pub fn delta_time(&self) -> Duration {
+        self.last_delta
+    }
+
+    /// Restarts from frame zero on the next tick.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Accumulates frame time into fixed-size update steps, so simulations
+/// advance identically regardless of the display refresh rate.
+#[derive(Debug, Clone)]
+pub struct FixedTimestep {
+    dt: Duration,
+    accumulator: Duration,
+    /// Most steps run per frame; the rest of a long stall is dropped rather
+    /// than spiralling into ever longer catch-up frames. Zero is treated as
+    /// one.
+    pub max_steps: u32,
+}
+
+impl FixedTimestep {
+    pub fn new(dt: Duration) -> Self {
+        Self {
+            dt: dt.max(Duration::from_nanos(1)),
+            accumulator: Duration::ZERO,
+            max_steps: 8,
+        }
+    }
+
+    /// Updates per second, e.g. `FixedTimestep::from_rate(60.0)`.
+    pub fn from_rate(hz: f64) -> Self {
+        Self::new(Duration::from_secs_f64(1.0 / hz.max(1e-6)))
+    }
+
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+
+    /// Adds a frame's delta time and returns how many updates to run.
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        self.accumulator += delta;
+        let mut steps = 0;
+        while self.accumulator >= self.dt {
+            self.accumulator -= self.dt;
+            steps += 1;
+            if steps >= self.max_steps.max(1) {
+                let rest = self.accumulator.as_nanos() % self.dt.as_nanos();
+                self.accumulator = Duration::from_nanos(rest as u64);
+                break;
+            }
+        }
+        steps
+    }
+
+    /// How far (`0.0..1.0`) the current frame lies between the last update
+    /// and the next, for interpolating rendered state.
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator.as_secs_f64() / self.dt.as_secs_f64()).min(1.0) as f32
+    }
+}
+
+/// Caps the frame rate by computing when the next frame may start.
+///
+/// An event loop passes [`next_frame_at`](Self::next_frame_at) to
+/// `ControlFlow::WaitUntil`; a plain loop can call [`wait`](Self::wait).
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    interval: Option<Duration>,
+    next: Option<Instant>,
+}
+
+impl FrameLimiter {
+    /// `None` (or a rate that is not positive, or too small to express as a
+    /// frame interval) disables the cap.
+    pub fn new(max_fps: Option<f64>) -> Self {
+        Self {
+            interval: max_fps
+                .filter(|fps| *fps > 0.0 && fps.is_finite())
+                .and_then(|fps| Duration::try_from_secs_f64(1.0 / fps).ok()),
+            next: None,
+        }
+    }
+
+    pub fn max_fps(&self) -> Option<f64> {
+        self.interval.map(|d| 1.0 / d.as_secs_f64())
+    }
+
+    /// Earliest time the next frame should start, `None` if uncapped or no
+    /// frame has started yet.
+    pub fn next_frame_at(&self) -> Option<Instant> {
+        self.next
+    }
+
+    /// Whether enough time has passed to start a frame at `now`.
+    pub fn is_ready(&self, now: Instant) -> bool {
+        self.next.is_none_or(|next| now >= next)
+    }
+
+    /// Records that a frame started at `now`.
+    pub fn frame_started(&mut self, now: Instant) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        // Schedule from the previous deadline to avoid drift, unless we fell
+        // behind by more than a whole frame.
+        let base = match self.next {
+            Some(next) if now.saturating_duration_since(next) < interval => next,
+            _ => now,
+        };
+        // A deadline past what `Instant` can represent leaves the next frame
+        // uncapped rather than panicking.
+        self.next = base.checked_add(interval);
+    }
+
+    /// Sleeps until the next frame may start, then records its start.
+    pub fn wait(&mut self) {
+        if let Some(next) = self.next {
+            let now = Instant::now();
+            if next > now {
+                std::thread::sleep(next - now);
+            }
+        }
+        self.frame_started(Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_timestep_caps_steps_per_frame() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(timestep.advance(Duration::from_millis(25)), 2);
+        assert!((timestep.alpha() - 0.5).abs() < 1e-6);
+        assert_eq!(timestep.advance(Duration::from_secs(1)), 8);
+        timestep.max_steps = 0;
+        assert_eq!(timestep.advance(Duration::from_secs(1)), 1);
+    }
+
+    #[test]
+    fn frame_limiter_handles_extreme_rates() {
+        assert_eq!(FrameLimiter::new(Some(1e-30)).max_fps(), None);
+        assert_eq!(FrameLimiter::new(Some(0.0)).max_fps(), None);
+        assert_eq!(FrameLimiter::new(Some(f64::NAN)).max_fps(), None);
+
+        let mut limiter = FrameLimiter::new(Some(1e-15));
+        let now = Instant::now();
+        assert!(limiter.is_ready(now));
+        limiter.frame_started(now);
+        assert!(limiter.next_frame_at().is_none_or(|next| next > now));
+    }
+
+    #[test]
+    fn frame_limiter_schedules_without_drift() {
+        let mut limiter = FrameLimiter::new(Some(100.0));
+        let start = Instant::now();
+        limiter.frame_started(start);
+        assert!(!limiter.is_ready(start + Duration::from_millis(5)));
+        limiter.frame_started(start + Duration::from_millis(12));
+        assert_eq!(
+            limiter.next_frame_at(),
+            Some(start + Duration::from_millis(20))
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Timeouts and intervals run on the event-loop cadence.
+//!
+//! The owner of the loop waits until [`Timers::next_deadline`] (e.g. via
+//! `ControlFlow::WaitUntil`) and then calls [`Timers::run_due`].
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+    id: TimerId,
+    /// `None` once the deadline lies beyond what `Instant` can represent;
+    /// such a timer never fires.
+    deadline: Option<Instant>,
+    /// `Some` for intervals.
+    period: Option<Duration>,
+    callback: Box<dyn FnMut() + Send>,
+}
+
+#[derive(Default)]
+pub struct Timers {
+    timers: Vec<Timer>,
+    next_id: u64,
+}
+
+impl fmt::Debug for Timers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timers")
+            .field("pending", &self.timers.len())
+            .finish()
+    }
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` once, `delay` from now. A delay too long to represent never
+    /// fires.
+    pub fn set_timeout(&mut self, delay: Duration, f: impl FnMut() + Send + 'static) -> TimerId {
+        self.insert(Instant::now().checked_add(delay), None, Box::new(f))
+    }
+
+    /// Runs `f` every `period`, starting `period` from now. A zero period is
+    /// treated as one millisecond so an interval cannot starve the loop.
+    pub fn set_interval(&mut self, period: Duration, f: impl FnMut() + Send + 'static) -> TimerId {
+        let period = period.max(Duration::from_millis(1));
+        self.insert(
+            Instant::now().checked_add(period),
+            Some(period),
+            Box::new(f),
+        )
+    }
+
+    fn insert(
+        &mut self,
+        deadline: Option<Instant>,
+        period: Option<Duration>,
+        callback: Box<dyn FnMut() + Send>,
+    ) -> TimerId {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+        self.timers.push(Timer {
+            id,
+            deadline,
+            period,
+            callback,
+        });
+        id
+    }
+
+    /// Cancels a timeout or interval. Returns false if it already finished.
+    pub fn clear(&mut self, id: TimerId) -> bool {
+        let before = self.timers.len();
+        self.timers.retain(|t| t.id != id);
+        self.timers.len() != before
+    }
+
+    /// When the loop next needs to wake up, if any timer is pending.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.timers.iter().filter_map(|t| t.deadline).min()
+    }
+
+    /// Runs every timer due at `now` in deadline order and reschedules
+    /// intervals. An interval that fell several periods behind runs once and
+    /// skips the missed ticks.
+    pub fn run_due(&mut self, now: Instant) {
+        let mut due: Vec<Timer> = Vec::new();
+        let mut i = 0;
+        while i < self.timers.len() {
+            if self.timers[i].deadline.is_some_and(|d| d <= now) {
+                due.push(self.timers.swap_remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        due.sort_by_key(|t| t.deadline);
+        for mut timer in due {
+            (timer.callback)();
+            if let (Some(period), Some(deadline)) = (timer.period, timer.deadline) {
+                timer.deadline = next_tick(deadline, period, now);
+                self.timers.push(timer);
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+}
+
+/// The first tick of an interval after `now`, skipping every missed period
+/// at once.
+fn next_tick(deadline: Instant, period: Duration, now: Instant) -> Option<Instant> {
+    let period_nanos = period.as_nanos();
+    let missed = now.saturating_duration_since(deadline).as_nanos() / period_nanos + 1;
+    let advance = missed.checked_mul(period_nanos)?;
+    let secs = u64::try_from(advance / 1_000_000_000).ok()?;
+    deadline.checked_add(Duration::new(secs, (advance % 1_000_000_000) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    fn counter() -> (Arc<AtomicUsize>, impl FnMut() + Send + 'static) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let inner = count.clone();
+        (count, move || {
+            inner.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn unrepresentable_timeouts_never_fire() {
+        let mut timers = Timers::new();
+        let (count, f) = counter();
+        let id = timers.set_timeout(Duration::MAX, f);
+        assert_eq!(timers.next_deadline(), None);
+        timers.run_due(Instant::now() + Duration::from_secs(3600));
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        assert!(timers.clear(id));
+    }
+
+    #[test]
+    fn late_intervals_run_once_and_skip_missed_ticks() {
+        let mut timers = Timers::new();
+        let (count, f) = counter();
+        timers.set_interval(Duration::from_millis(10), f);
+        let first = timers.next_deadline().unwrap();
+        let now = first + Duration::from_secs(1_000_000) + Duration::from_millis(3);
+        timers.run_due(now);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(timers.next_deadline(), Some(now + Duration::from_millis(7)));
+    }
+
+    #[test]
+    fn timeouts_run_once_in_deadline_order() {
+        let mut timers = Timers::new();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for (i, delay) in [(0, 20), (1, 10)] {
+            let order = order.clone();
+            timers.set_timeout(Duration::from_millis(delay), move || {
+                order.lock().unwrap().push(i)
+            });
+        }
+        let later = Instant::now() + Duration::from_secs(1);
+        timers.run_due(later);
+        timers.run_due(later);
+        assert_eq!(*order.lock().unwrap(), vec![1, 0]);
+        assert!(timers.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+//! Raster-to-vector tracing of bitmap shapes into closed outlines.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    color::Color,
+    export::ExportOptions,
+    geometry::{Point, Rect},
+    image::Image,
+    mask::Mask,
+    shape::Shape,
+};
+
+/// Outlines of the dark parts of `image`: pixels whose luminance, composited
+/// over white, is below `threshold` (`0.0..=1.0`).
+pub fn trace_bitmap(image: &Image, threshold: f32) -> Vec<Vec<Point>> {
+    let mut mask = Mask::new(image.width(), image.height());
+    for y in 0..image.height() {
+        for x in 0..image.width() {
+            let c = image.get(x, y).unwrap_or_default();
+            let luma = 0.2126 * c.r + 0.7152 * c.g + 0.0722 * c.b;
+            if luma * c.a + (1.0 - c.a) < threshold {
+                mask.set(x, y, true);
+            }
+        }
+    }
+    trace_mask(&mask)
+}
+
+/// Outlines of the selected regions of `mask`, following pixel edges.
+///
+/// Outer boundaries run clockwise on the canvas and holes counter-clockwise,
+/// so the outlines fill correctly with either fill rule. Diagonally touching
+/// pixels are treated as separate regions. Collinear vertices are merged.
+pub fn trace_mask(mask: &Mask) -> Vec<Vec<Point>> {
+    // Directed boundary edges with the selected pixel on their right.
+    let mut edges: HashMap<(i32, i32), Vec<(i32, i32)>> = HashMap::new();
+    let mut add = |a: (i32, i32), b: (i32, i32)| edges.entry(a).or_default().push(b);
+    for (x, y) in mask.iter() {
+        let filled = |dx: i32, dy: i32| {
+            let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+            nx >= 0 && ny >= 0 && mask.get(nx as u32, ny as u32)
+        };
+        let (x, y) = (x as i32, y as i32);
+        if !filled(0, -1) {
+            add((x, y), (x + 1, y));
+        }
+        if !filled(1, 0) {
+            add((x + 1, y), (x + 1, y + 1));
+        }
+        if !filled(0, 1) {
+            add((x + 1, y + 1), (x, y + 1));
+        }
+        if !filled(-1, 0) {
+            add((x, y + 1), (x, y));
+        }
+    }
+
+    let mut starts: Vec<_> = edges.keys().copied().collect();
+    starts.sort_unstable_by_key(|&(x, y)| (y, x));
+    let mut outlines = Vec::new();
+    for start in starts {
+        while let Some(first) = edges.get_mut(&start).and_then(Vec::pop) {
+            let mut loop_points = vec![start];
+            let (mut prev, mut current) = (start, first);
+            while current != start {
+                loop_points.push(current);
+                let dir = (current.0 - prev.0, current.1 - prev.1);
+                let next = take_next(&mut edges, current, dir);
+                prev = current;
+                current = next;
+            }
+            outlines.push(merge_collinear(&loop_points));
+        }
+    }
+    outlines
+}
+
+/// Removes and returns the outgoing edge at `v`, preferring a right turn at
+/// saddle points so diagonal neighbors stay separate.
+fn take_next(
+    edges: &mut HashMap<(i32, i32), Vec<(i32, i32)>>,
+    v: (i32, i32),
+    dir: (i32, i32),
+) -> (i32, i32) {
+    let out = edges
+        .get_mut(&v)
+        .expect("boundary edges always form closed loops");
+    let right = (v.0 - dir.1, v.1 + dir.0);
+    let i = out.iter().position(|&e| e == right).unwrap_or(0);
+    out.swap_remove(i)
+}
+
+fn merge_collinear(points: &[(i32, i32)]) -> Vec<Point> {
+    let n = points.len();
+    (0..n)
+        .filter(|&i| {
+            let (a, b, c) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+            (b.0 - a.0, b.1 - a.1) != (c.0 - b.0, c.1 - b.1)
+        })
+        .map(|i| Point::new(points[i].0 as f32, points[i].1 as f32))
+        .collect()
+}
+
+/// The outlines as polygon shapes.
+pub fn outline_shapes(outlines: &[Vec<Point>]) -> Vec<Shape> {
+    outlines.iter().cloned().map(Shape::Polygon).collect()
+}
+
+/// SVG path data (`d` attribute) for the outlines, one closed subpath each.
+///
+/// Use `fill-rule="evenodd"` or `"nonzero"`; both render holes correctly.
+pub fn svg_path_data(outlines: &[Vec<Point>]) -> String {
+    let mut d = String::new();
+    for outline in outlines {
+        for (i, p) in outline.iter().enumerate() {
+            let cmd = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(d, "{cmd}{} {}", p.x, p.y);
+        }
+        if !outline.is_empty() {
+            d.push('Z');
+        }
+    }
+    d
+}
+
+/// A standalone SVG document filling the outlines in `fill`, framed by
+/// `options` exactly as [`ExportOptions::apply`] frames a raster of the same
+/// `canvas`: trimmed to the outline bounds, padded, flattened onto the
+/// background and scaled.
+pub fn svg_document(
+    outlines: &[Vec<Point>],
+    fill: Color,
+    canvas: Rect,
+    options: &ExportOptions,
+) -> String {
+    let content = Rect::from_points(outlines.iter().flatten().copied());
+    let frame = options.frame(canvas, content);
+    let scale = options.output_scale();
+    let framed: Vec<Vec<Point>> = outlines
+        .iter()
+        .map(|outline| {
+            outline
+                .iter()
+                .map(|p| Point::new((p.x - frame.x) * scale, (p.y - frame.y) * scale))
+                .collect()
+        })
+        .collect();
+    let (w, h) = (frame.width * scale, frame.height * scale);
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#
+    );
+    if let Some(background) = options.background {
+        let _ = write!(
+            svg,
+            r#"<rect width="{w}" height="{h}" {}/>"#,
+            svg_paint("fill", background)
+        );
+    }
+    let _ = write!(
+        svg,
+        r#"<path d="{}" fill-rule="evenodd" {}/>"#,
+        svg_path_data(&framed),
+        svg_paint("fill", fill)
+    );
+    svg.push_str("</svg>");
+    svg
+}
+
+/// `fill="#rrggbb" fill-opacity="a"` style attributes for a color.
+fn svg_paint(attribute: &str, color: Color) -> String {
+    let [r, g, b, _] = color.to_rgba8();
+    format!(
+        r##"{attribute}="#{r:02x}{g:02x}{b:02x}" {attribute}-opacity="{}""##,
+        color.a
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shoelace area; positive for clockwise loops on the y-down canvas.
+    fn signed_area(outline: &[Point]) -> f32 {
+        let n = outline.len();
+        (0..n)
+            .map(|i| {
+                let (a, b) = (outline[i], outline[(i + 1) % n]);
+                a.x * b.y - b.x * a.y
+            })
+            .sum::<f32>()
+            / 2.0
+    }
+
+    fn mask_from(rows: &[&str]) -> Mask {
+        let mut mask = Mask::new(rows[0].len() as u32, rows.len() as u32);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                mask.set(x as u32, y as u32, c == '#');
+            }
+        }
+        mask
+    }
+
+    #[test]
+    fn holes_run_opposite_to_outer_boundaries() {
+        let mask = mask_from(&["#####", "#####", "##.##", "#####", "#####"]);
+        let mut areas: Vec<f32> = trace_mask(&mask).iter().map(|o| signed_area(o)).collect();
+        areas.sort_by(f32::total_cmp);
+        assert_eq!(areas, vec![-1.0, 25.0]);
+    }
+
+    #[test]
+    fn merges_collinear_vertices() {
+        let mask = mask_from(&["###", "###"]);
+        let outlines = trace_mask(&mask);
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(outlines[0].len(), 4);
+    }
+
+    #[test]
+    fn diagonal_neighbors_are_separate_regions() {
+        let mask = mask_from(&["#.", ".#"]);
+        let outlines = trace_mask(&mask);
+        assert_eq!(outlines.len(), 2);
+        assert!(outlines.iter().all(|o| signed_area(o) == 1.0));
+    }
+
+    #[test]
+    fn threshold_selects_dark_pixels() {
+        let mut image = Image::new(3, 1, Color::WHITE);
+        image.set(1, 0, Color::BLACK);
+        let outlines = trace_bitmap(&image, 0.5);
+        assert_eq!(outlines.len(), 1);
+        assert_eq!(signed_area(&outlines[0]), 1.0);
+    }
+
+    #[test]
+    fn svg_path_closes_each_outline() {
+        let outlines = trace_mask(&mask_from(&["#"]));
+        assert_eq!(svg_path_data(&outlines), "M0 0L1 0L1 1L0 1Z");
+    }
+
+    #[test]
+    fn svg_document_is_framed_like_raster_exports() {
+        let square = vec![vec![
+            Point::new(2.0, 3.0),
+            Point::new(3.0, 3.0),
+            Point::new(3.0, 4.0),
+            Point::new(2.0, 4.0),
+        ]];
+        let canvas = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let options = ExportOptions {
+            trim_to_content: true,
+            padding: 1,
+            background: Some(Color::WHITE),
+            scale: 2.0,
+        };
+        let svg = svg_document(&square, Color::BLACK, canvas, &options);
+        assert!(svg.contains(r#"width="6" height="6" viewBox="0 0 6 6""#));
+        assert!(svg.contains(r##"<rect width="6" height="6" fill="#ffffff" fill-opacity="1"/>"##));
+        assert!(svg.contains(r#"d="M2 2L4 2L4 4L2 4Z""#));
+
+        // Without trimming the page is the whole canvas.
+        let svg = svg_document(&square, Color::BLACK, canvas, &ExportOptions::default());
+        assert!(svg.contains(r#"viewBox="0 0 10 10""#));
+        assert!(svg.contains(r#"d="M2 3L3 3L3 4L2 4Z""#));
+        assert!(!svg.contains("<rect"));
+    }
+}
@@ -0,0 +1,190 @@
+//! Tweened animation of retained shapes, advanced once per frame.
+
+use std::time::Duration;
+
+use crate::{
+    color::Color,
+    easing::Easing,
+    geometry::Point,
+    scene::{Scene, ShapeId},
+};
+
+/// An animatable property and its target value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Property {
+    /// Moves the shape so the center of its bounds reaches the point.
+    Position(Point),
+    Color(Color),
+}
+
+#[derive(Debug, Clone)]
+struct Tween {
+    id: ShapeId,
+    target: Property,
+    /// Captured from the scene on the first update.
+    start: Option<Property>,
+    duration: Duration,
+    elapsed: Duration,
+    easing: Easing,
+}
+
+/// The set of running tweens.
+#[derive(Debug, Clone, Default)]
+pub struct Tweens {
+    tweens: Vec<Tween>,
+}
+
+impl Tweens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Animates a property of `id` to `target` over `duration`. A running
+    /// tween of the same property on the same shape is replaced and the new
+    /// one starts from the current value.
+    pub fn animate(&mut self, id: ShapeId, target: Property, duration: Duration, easing: Easing) {
+        self.tweens.retain(|t| {
+            t.id != id || std::mem::discriminant(&t.target) != std::mem::discriminant(&target)
+        });
+        self.tweens.push(Tween {
+            id,
+            target,
+            start: None,
+            duration,
+            elapsed: Duration::ZERO,
+            easing,
+        });
+    }
+
+    /// Stops every tween on `id`, leaving the shape where it is.
+    pub fn cancel(&mut self, id: ShapeId) {
+        self.tweens.retain(|t| t.id != id);
+    }
+
+    pub fn is_animating(&self, id: ShapeId) -> bool {
+        self.tweens.iter().any(|t| t.id == id)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tweens.is_empty()
+    }
+
+    /// Advances every tween by `delta` and writes the new values into
+    /// `scene`. Finished tweens, and tweens whose shape was removed, are
+    /// dropped.
+    pub fn update(&mut self, scene: &mut Scene, delta: Duration) {
+        self.tweens.retain_mut(|tween| {
+            let Some(item) = scene.get_mut(tween.id) else {
+                return false;
+            };
+            let start = *tween.start.get_or_insert(match tween.target {
+                Property::Position(_) => {
+                    Property::Position(item.shape.bounds().unwrap_or_default().center())
+                }
+                Property::Color(_) => Property::Color(item.color),
+            });
+            tween.elapsed += delta;
+            let t = if tween.duration.is_zero() {
+                1.0
+            } else {
+                tween.elapsed.as_secs_f32() / tween.duration.as_secs_f32()
+            };
+            // Finished tweens land exactly on the target, whatever the easing.
+            let e = if t >= 1.0 { 1.0 } else { tween.easing.apply(t) };
+            let lerp = |a: f32, b: f32| a * (1.0 - e) + b * e;
+            match (start, tween.target) {
+                (Property::Position(from), Property::Position(to)) => {
+                    let current = item.shape.bounds().unwrap_or_default().center();
+                    let x = lerp(from.x, to.x);
+                    let y = lerp(from.y, to.y);
+                    item.shape.translate(x - current.x, y - current.y);
+                }
+                (Property::Color(from), Property::Color(to)) => {
+                    item.color = Color::rgba(
+                        lerp(from.r, to.r),
+                        lerp(from.g, to.g),
+                        lerp(from.b, to.b),
+                        lerp(from.a, to.a),
+                    );
+                }
+                _ => unreachable!("start is captured from the target's property"),
+            }
+            t < 1.0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Rect;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn center(scene: &Scene, id: ShapeId) -> Point {
+        scene.get(id).unwrap().shape.bounds().unwrap().center()
+    }
+
+    #[test]
+    fn tweens_reach_their_target_exactly() {
+        let mut scene = Scene::new();
+        let id = scene.add_rect(
+            Rect::new(0.0, 0.0, 2.0, 2.0),
+            Color::rgba(0.3, 0.7, 0.1, 0.6),
+        );
+        let mut tweens = Tweens::new();
+        let target = Point::new(10.3, -7.1);
+        tweens.animate(id, Property::Position(target), ms(90), Easing::CubicInOut);
+        tweens.animate(id, Property::Color(Color::RED), ms(90), Easing::Linear);
+        for _ in 0..4 {
+            tweens.update(&mut scene, ms(30));
+        }
+        // Moving a shape is a translation, so the center is only as exact as
+        // the float arithmetic; colors are set outright.
+        assert!(center(&scene, id).distance(target) < 1e-5);
+        assert_eq!(scene.get(id).unwrap().color, Color::RED);
+        assert!(tweens.is_empty());
+    }
+
+    #[test]
+    fn replacing_a_tween_restarts_from_the_current_value() {
+        let mut scene = Scene::new();
+        let id = scene.add_circle((0.0, 0.0), 1.0, Color::BLACK);
+        let mut tweens = Tweens::new();
+        tweens.animate(
+            id,
+            Property::Position(Point::new(100.0, 0.0)),
+            ms(100),
+            Easing::Linear,
+        );
+        tweens.update(&mut scene, ms(50));
+        assert_eq!(center(&scene, id), Point::new(50.0, 0.0));
+
+        tweens.animate(
+            id,
+            Property::Position(Point::new(50.0, 100.0)),
+            ms(100),
+            Easing::Linear,
+        );
+        tweens.update(&mut scene, ms(50));
+        assert_eq!(center(&scene, id), Point::new(50.0, 50.0));
+        tweens.update(&mut scene, ms(50));
+        assert_eq!(center(&scene, id), Point::new(50.0, 100.0));
+    }
+
+    #[test]
+    fn tweens_of_removed_shapes_are_dropped() {
+        let mut scene = Scene::new();
+        let id = scene.add_point((0.0, 0.0), Color::BLACK);
+        let mut tweens = Tweens::new();
+        tweens.animate(id, Property::Color(Color::WHITE), ms(100), Easing::Linear);
+        tweens.update(&mut scene, ms(10));
+        assert!(tweens.is_animating(id));
+        scene.remove_shape(id);
+        tweens.update(&mut scene, ms(10));
+        assert!(!tweens.is_animating(id));
+        assert!(tweens.is_empty());
+    }
+}